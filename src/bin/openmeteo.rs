@@ -1,16 +1,25 @@
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::PathBuf;
+use std::time::Duration;
+
 use chrono::{DateTime, FixedOffset, Local, Timelike};
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use itertools::Itertools;
 use serde::Serialize;
 
 use openmeteo::data::{
-    format_precip, format_temp, format_wmo_symbol, Current, Forecast, WeatherPoint,
+    format_aqi, format_precip, format_temp, format_wind, format_wmo_symbol, stitch_historical,
+    AirQualityPoint, CompactOptions, Coord, Current, Forecast, Report, Units, WeatherPoint,
     MAX_FORECAST_DAYS,
 };
-use openmeteo::fetch::{download_current, download_forecast};
-use openmeteo::location::resolve_location;
-use openmeteo::table::Table;
-use time::{parse_date_range, resolve_time_range};
+use openmeteo::fetch::{
+    download_air_quality, download_current, download_forecast, download_forecasts,
+    download_historical, watch_current,
+};
+use openmeteo::location::{resolve_current_location, resolve_location, Location};
+use openmeteo::table::{detect_terminal_width, parse_fixed_width, Table, TableStyle};
+use openmeteo::time::{parse_date_range, resolve_time_range, resolve_timezone};
 
 #[derive(Parser)]
 #[command(name = "openmeteo")]
@@ -25,8 +34,13 @@ struct Cli {
 enum Command {
     /// Fetch weather forecast for a given location and dates
     Forecast {
-        /// Location name or lat,long pair
-        location: String,
+        /// Location name or lat,long pair; omit to autolocate from your IP address
+        location: Option<String>,
+
+        /// Additional location to compare, in a single batched request; repeatable. When given,
+        /// the positional location (if any) is included as one of the batch too
+        #[arg(long = "location", value_name = "LOCATION")]
+        locations: Vec<String>,
 
         /// Date or range: YYYY-MM-DD, +N, 'today', 'tomorrow', weekday, or date1..date2
         #[arg(default_value = "today", value_name = "DATE_RANGE")]
@@ -48,25 +62,107 @@ enum Command {
         #[arg(long)]
         json: bool,
 
+        /// Output a single pretty-printed JSON object carrying the full forecast plus a
+        /// data-source attribution line, instead of a formatted table or --json's JSON lines
+        #[arg(long)]
+        report: bool,
+
         /// Verbose output
         #[arg(short, long)]
         verbose: bool,
+
+        /// IANA timezone to interpret dates in (e.g. "America/New_York").
+        /// Defaults to the `TZ` environment variable, then the host's local
+        /// timezone, falling back to UTC if neither can be determined.
+        #[arg(long)]
+        timezone: Option<String>,
+
+        /// Unit system for temperature, precipitation, and wind speed
+        #[arg(long, value_enum, default_value_t = Units::Metric)]
+        units: Units,
+
+        /// Number of days to fetch from Open-Meteo (fewer days means less data to download)
+        #[arg(long, default_value_t = MAX_FORECAST_DAYS, value_parser = clap::value_parser!(u8).range(1..=MAX_FORECAST_DAYS as i64))]
+        days: u8,
+
+        /// Also fetch PM2.5, European AQI, and UV index from Open-Meteo's air-quality API
+        #[arg(long)]
+        air_quality: bool,
+
+        /// Don't wrap wide columns to fit the terminal width
+        #[arg(long)]
+        no_wrap: bool,
+
+        /// Table border/layout style
+        #[arg(long, value_enum, default_value_t = TableStyle::Bare)]
+        style: TableStyle,
+
+        /// Bucket size in hours for compacting non-today forecast points (must evenly divide 24);
+        /// has no effect with --full
+        #[arg(long, default_value_t = CompactOptions::default().bucket_hours, value_parser = clap::value_parser!(u8).range(1..=24))]
+        bucket_hours: u8,
     },
     /// Fetch current weather for a given location
     Current {
-        /// Location name or lat,long pair
-        location: String,
+        /// Location name or lat,long pair; omit to autolocate from your IP address
+        location: Option<String>,
 
         /// Output raw JSON instead of formatted table
         #[arg(long)]
         json: bool,
 
+        /// Output a single pretty-printed JSON object carrying the current weather plus a
+        /// data-source attribution line, instead of a formatted table or --json's JSON lines
+        #[arg(long)]
+        report: bool,
+
         /// Verbose output
         #[arg(short, long)]
         verbose: bool,
+
+        /// Unit system for temperature, precipitation, and wind speed
+        #[arg(long, value_enum, default_value_t = Units::Metric)]
+        units: Units,
+
+        /// Table border/layout style
+        #[arg(long, value_enum, default_value_t = TableStyle::Bare)]
+        style: TableStyle,
+
+        /// Keep polling every SECONDS and reprint on each update, instead of fetching once
+        #[arg(long, value_name = "SECONDS")]
+        watch: Option<u64>,
+    },
+    /// Reparse whitespace-aligned tabular text (e.g. this tool's own table output, or
+    /// third-party command output) and re-emit it as CSV or JSON
+    Reformat {
+        /// Path to the tabular text to reparse; reads stdin if omitted
+        file: Option<PathBuf>,
+
+        /// The first line is data, not a header
+        #[arg(long)]
+        no_header: bool,
+
+        /// Only keep rows whose COLUMN value contains SUBSTRING (COLUMN=SUBSTRING)
+        #[arg(long, value_name = "COLUMN=SUBSTRING")]
+        filter: Option<String>,
+
+        /// Sort rows by this column's values
+        #[arg(long, value_name = "COLUMN")]
+        sort_by: Option<String>,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = ReformatFormat::Csv)]
+        format: ReformatFormat,
     },
 }
 
+/// Output format for the `reformat` subcommand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ReformatFormat {
+    Csv,
+    Json,
+}
+
 /// Dedup consecutive identical values, replacing duplicates with empty strings
 /// e.g. `dedup(["foo", "foo", "foo", "bar", "bar", "baz"]) == ["foo", "", "", "bar", "", "baz"]`.
 fn dedup(items: impl IntoIterator<Item = String>) -> Vec<String> {
@@ -84,11 +180,17 @@ fn dedup(items: impl IntoIterator<Item = String>) -> Vec<String> {
 ///
 /// Filters the forecast data to the requested time range, then constructs a table with Date and
 /// Hour columns on the left, followed by weather symbol, temperature, and precipitation columns
-/// for each model. Dates are deduped so only the first row of each day shows the date.
+/// for each model. Dates are deduped so only the first row of each day shows the date. A footer
+/// row reports each model's total precipitation over the displayed range. The table is rendered
+/// in `style`, wrapped to `max_width` columns if the natural layout doesn't fit.
 fn build_forecast_table(
     time_points: &[DateTime<FixedOffset>],
     by_model: &[(String, Vec<WeatherPoint>)],
     (start_time, end_time): (DateTime<FixedOffset>, DateTime<FixedOffset>),
+    units: Units,
+    air_quality: Option<&[AirQualityPoint]>,
+    style: TableStyle,
+    max_width: Option<usize>,
 ) -> Table {
     let in_range = |dt| dt >= start_time && dt < end_time;
 
@@ -108,11 +210,14 @@ fn build_forecast_table(
         .collect();
 
     let mut table = Table::new().column("Date", dates).column("Hour", hours);
+    let mut footer = vec![("Total precip".to_string(), 2)];
 
     for (model, weather_points) in by_model {
         let mut symbols = Vec::new();
         let mut temps = Vec::new();
         let mut precips = Vec::new();
+        let mut winds = Vec::new();
+        let mut total_precip = 0.0;
 
         for (i, &time) in time_points.iter().enumerate() {
             if !in_range(time) {
@@ -123,43 +228,174 @@ fn build_forecast_table(
                 weather.and_then(|w| w.code),
                 time.hour() as u8,
             ));
-            temps.push(format_temp(weather.and_then(|w| w.temp)));
-            precips.push(format_precip(weather.and_then(|w| w.precip)));
+            temps.push(format_temp(weather.and_then(|w| w.temp), units));
+            precips.push(format_precip(weather.and_then(|w| w.precip), units));
+            winds.push(format_wind(
+                weather.and_then(|w| w.wind_speed),
+                weather.and_then(|w| w.wind_dir),
+                units,
+            ));
+            total_precip += weather.and_then(|w| w.precip).unwrap_or(0.0);
         }
 
         table = table
             .group(model)
             .column("", symbols)
             .column("Temp", temps)
-            .column("Precip", precips);
+            .column("Precip", precips)
+            .column("Wind", winds);
+        footer.push((format_precip(Some(total_precip), units), 4));
     }
 
-    table
+    if let Some(air_quality) = air_quality {
+        let mut aqis = Vec::new();
+        let mut uv_indices = Vec::new();
+
+        for (i, &time) in time_points.iter().enumerate() {
+            if !in_range(time) {
+                continue;
+            }
+            let point = air_quality.get(i);
+            aqis.push(format_aqi(point.and_then(|p| p.aqi)));
+            uv_indices.push(match point.and_then(|p| p.uv_index) {
+                Some(uv) => format!("{uv:.1}"),
+                None => "-".to_string(),
+            });
+        }
+
+        table = table
+            .group("Air Quality")
+            .column("AQI", aqis)
+            .column("UV", uv_indices);
+        footer.push((String::new(), 2));
+    }
+
+    table.footer_row(footer).style(style).max_width(max_width)
+}
+
+/// Resolve the `location` argument, if given, or fall back to the caller's approximate
+/// location via IP geolocation so the CLI can be run with zero arguments.
+async fn resolve_location_arg(location: Option<&str>) -> anyhow::Result<Location> {
+    match location {
+        Some(s) => resolve_location(s),
+        None => resolve_current_location().await,
+    }
+}
+
+/// Shared forecast-related flags for `do_forecast`/`do_forecast_batch`, bundled so adding
+/// another `forecast` flag doesn't grow either function's argument list.
+struct ForecastOptions<'a> {
+    models: &'a [String],
+    full: bool,
+    json: bool,
+    report: bool,
+    verbose: bool,
+    timezone: Option<&'a str>,
+    units: Units,
+    days: u8,
+    air_quality: bool,
+    no_wrap: bool,
+    style: TableStyle,
+    bucket_hours: u8,
 }
 
 /// Handle the `forecast` subcommand: fetch and display weather forecast.
 ///
-/// Resolves the location (by name or coordinates), parses the date range, downloads forecast data
-/// from Open-Meteo for the requested models, and prints the result as a formatted table.
+/// Resolves the location (by name or coordinates, or autolocated from the caller's IP if
+/// omitted), parses the date range, downloads forecast data from Open-Meteo for the
+/// requested models, and prints the result as a formatted table. When the range starts before
+/// today, the past portion is served from the historical archive instead of the forecast
+/// models, and stitched together with any future portion into one table.
 async fn do_forecast(
-    location: &str,
+    location: Option<&str>,
     dates: &str,
-    models: &[String],
-    full: bool,
-    json: bool,
-    verbose: bool,
+    options: &ForecastOptions<'_>,
 ) -> anyhow::Result<()> {
-    let location = resolve_location(location).await?;
+    let &ForecastOptions {
+        models,
+        full,
+        json,
+        report,
+        verbose,
+        timezone,
+        units,
+        days,
+        air_quality,
+        no_wrap,
+        style,
+        bucket_hours,
+    } = options;
+
+    let location = resolve_location_arg(location).await?;
     let date_range = parse_date_range(dates)?;
+    let timezone = resolve_timezone(timezone)?;
+
+    let now = Local::now().with_timezone(&timezone).fixed_offset();
+    let time_range = resolve_time_range(date_range, timezone, now);
+    let today = now.date_naive();
+
+    let historical = if time_range.0.date_naive() < today {
+        let last_included_date = (time_range.1 - chrono::Duration::seconds(1)).date_naive();
+        let end_date = std::cmp::min(last_included_date, today - chrono::Duration::days(1));
+        Some(
+            download_historical(
+                location.latitude,
+                location.longitude,
+                time_range.0.date_naive(),
+                end_date,
+                units,
+            )
+            .await?,
+        )
+    } else {
+        None
+    };
 
     let models: Vec<&str> = models.iter().map(|s| s.as_str()).collect();
-    let mut forecast = download_forecast(location.latitude, location.longitude, &models).await?;
+    let forecast = if time_range.1 > now {
+        let mut forecast =
+            download_forecast(location.latitude, location.longitude, &models, units, days).await?;
+
+        if air_quality {
+            let (aq_times, aq_points) =
+                download_air_quality(location.latitude, location.longitude, days).await?;
+            let aq_by_time: HashMap<DateTime<FixedOffset>, AirQualityPoint> =
+                aq_times.into_iter().zip(aq_points).collect();
+            forecast.air_quality = Some(
+                forecast
+                    .times
+                    .iter()
+                    .map(|t| {
+                        aq_by_time.get(t).cloned().unwrap_or(AirQualityPoint {
+                            pm2_5: None,
+                            aqi: None,
+                            uv_index: None,
+                        })
+                    })
+                    .collect(),
+            );
+        }
+        Some(forecast)
+    } else {
+        None
+    };
 
-    let now = Local::now()
-        .with_timezone(&forecast.timezone)
-        .fixed_offset();
+    let mut forecast = match (historical, forecast) {
+        (Some(h), Some(f)) => stitch_historical(h, f),
+        (Some(h), None) => h,
+        (None, Some(f)) => f,
+        (None, None) => unreachable!("time range always has a past or future portion"),
+    };
 
-    let time_range = resolve_time_range(date_range, forecast.timezone, now);
+    if report {
+        let forecast_timezone = forecast.timezone;
+        let forecast_location = forecast.location.clone();
+        println!(
+            "{}",
+            Report::new(forecast, forecast_timezone, forecast_location).to_pretty_json()?
+        );
+        return Ok(());
+    }
 
     if json {
         print_forecast_json(&forecast, time_range);
@@ -169,13 +405,152 @@ async fn do_forecast(
     println!("Forecast for {}", location.display_name);
     if verbose {
         println!("Grid-cell location: {}", forecast.location.link());
-        println!("Timezone: {}", forecast.timezone);
+        println!("Grid-cell timezone: {}", forecast.timezone);
+        println!("Display timezone: {timezone}");
         println!("Interval: [{}, {})", time_range.0, time_range.1);
     }
     if !full {
-        forecast.compact(now.date_naive());
+        forecast.compact_with(
+            now.date_naive(),
+            CompactOptions {
+                bucket_hours,
+                ..CompactOptions::default()
+            },
+        )?;
+    }
+    let max_width = if no_wrap {
+        None
+    } else {
+        Some(detect_terminal_width())
+    };
+    build_forecast_table(
+        &forecast.times,
+        &forecast.by_model,
+        time_range,
+        units,
+        forecast.air_quality.as_deref(),
+        style,
+        max_width,
+    )
+    .print();
+    Ok(())
+}
+
+/// Handle the `forecast` subcommand when one or more `--location` flags are given in addition
+/// to (or instead of) the positional location: resolve every location, fetch them all in a
+/// single batched Open-Meteo request, and print each forecast as its own table (or JSON lines,
+/// or report), prefixed with its location name.
+async fn do_forecast_batch(
+    location: Option<&str>,
+    locations: &[String],
+    dates: &str,
+    options: &ForecastOptions<'_>,
+) -> anyhow::Result<()> {
+    let &ForecastOptions {
+        models,
+        full,
+        json,
+        report,
+        verbose,
+        timezone,
+        units,
+        days,
+        air_quality,
+        no_wrap,
+        style,
+        bucket_hours,
+    } = options;
+
+    let location_strs: Vec<&str> = location
+        .into_iter()
+        .chain(locations.iter().map(String::as_str))
+        .collect();
+    let resolved: Vec<Location> = location_strs
+        .iter()
+        .map(|s| resolve_location(s))
+        .collect::<anyhow::Result<_>>()?;
+    let coords: Vec<Coord> = resolved
+        .iter()
+        .map(|loc| Coord {
+            latitude: loc.latitude,
+            longitude: loc.longitude,
+        })
+        .collect();
+
+    let date_range = parse_date_range(dates)?;
+    let timezone = resolve_timezone(timezone)?;
+    let models: Vec<&str> = models.iter().map(|s| s.as_str()).collect();
+    let mut forecasts = download_forecasts(&coords, &models, units, days).await?;
+
+    if air_quality {
+        for (coord, forecast) in coords.iter().zip(&mut forecasts) {
+            let (aq_times, aq_points) =
+                download_air_quality(coord.latitude, coord.longitude, days).await?;
+            let aq_by_time: HashMap<DateTime<FixedOffset>, AirQualityPoint> =
+                aq_times.into_iter().zip(aq_points).collect();
+            forecast.air_quality = Some(
+                forecast
+                    .times
+                    .iter()
+                    .map(|t| {
+                        aq_by_time.get(t).cloned().unwrap_or(AirQualityPoint {
+                            pm2_5: None,
+                            aqi: None,
+                            uv_index: None,
+                        })
+                    })
+                    .collect(),
+            );
+        }
+    }
+
+    let now = Local::now().with_timezone(&timezone).fixed_offset();
+    let time_range = resolve_time_range(date_range, timezone, now);
+    let max_width = if no_wrap {
+        None
+    } else {
+        Some(detect_terminal_width())
+    };
+
+    for (location, mut forecast) in resolved.iter().zip(forecasts) {
+        if !full {
+            forecast.compact_with(
+                now.date_naive(),
+                CompactOptions {
+                    bucket_hours,
+                    ..CompactOptions::default()
+                },
+            )?;
+        }
+        if report {
+            let forecast_timezone = forecast.timezone;
+            let forecast_location = forecast.location.clone();
+            println!(
+                "{}",
+                Report::new(forecast, forecast_timezone, forecast_location).to_pretty_json()?
+            );
+            continue;
+        }
+        if json {
+            print_forecast_json(&forecast, time_range);
+            continue;
+        }
+        println!("Forecast for {}", location.display_name);
+        if verbose {
+            println!("Grid-cell location: {}", forecast.location.link());
+            println!("Grid-cell timezone: {}", forecast.timezone);
+        }
+        build_forecast_table(
+            &forecast.times,
+            &forecast.by_model,
+            time_range,
+            units,
+            forecast.air_quality.as_deref(),
+            style,
+            max_width,
+        )
+        .print();
     }
-    build_forecast_table(&forecast.times, &forecast.by_model, time_range).print();
     Ok(())
 }
 
@@ -226,19 +601,116 @@ fn print_forecast_json(
     }
 }
 
+/// Display flags shared by `do_current`'s two `print_current` call sites (once and `--watch`),
+/// bundled so adding an output flag doesn't grow `print_current`'s argument list.
+struct CurrentDisplayOptions {
+    json: bool,
+    report: bool,
+    verbose: bool,
+    style: TableStyle,
+}
+
 /// Handle the `current` subcommand: fetch and display current weather.
 ///
-/// Resolves the location (by name or coordinates), downloads current weather from Open-Meteo,
-/// and prints the result as a single-row table.
-async fn do_current(location: &str, json: bool, verbose: bool) -> anyhow::Result<()> {
-    let location = resolve_location(location).await?;
-    let current = download_current(location.latitude, location.longitude).await?;
+/// Resolves the location (by name or coordinates, or autolocated from the caller's IP if
+/// omitted), then either downloads current weather once, or (with `watch`) polls it on a
+/// fixed interval and reprints on every update until the process is killed. Either way, each
+/// reading is printed as a single-row table, or a JSON line with `json`.
+async fn do_current(
+    location: Option<&str>,
+    json: bool,
+    report: bool,
+    verbose: bool,
+    units: Units,
+    style: TableStyle,
+    watch: Option<u64>,
+) -> anyhow::Result<()> {
+    let location = resolve_location_arg(location).await?;
+    let timezone = resolve_timezone(None)?;
+    let display = CurrentDisplayOptions {
+        json,
+        report,
+        verbose,
+        style,
+    };
 
+    let Some(interval_secs) = watch else {
+        let current = download_current(location.latitude, location.longitude, units).await?;
+        let trend_forecast = download_forecast(
+            location.latitude,
+            location.longitude,
+            &["ecmwf_ifs"],
+            units,
+            2,
+        )
+        .await?;
+        let trend = current.trend(&trend_forecast, units.trend_threshold());
+        print_current(&location.display_name, current, trend, timezone, &display)?;
+        return Ok(());
+    };
+
+    let mut updates = watch_current(
+        location.latitude,
+        location.longitude,
+        units,
+        Duration::from_secs(interval_secs),
+    );
+    while let Some(result) = updates.recv().await {
+        let current = match result {
+            Ok(current) => current,
+            Err(err) => {
+                eprintln!("Error: {err:#}");
+                continue;
+            }
+        };
+        match download_forecast(
+            location.latitude,
+            location.longitude,
+            &["ecmwf_ifs"],
+            units,
+            2,
+        )
+        .await
+        {
+            Ok(trend_forecast) => {
+                let trend = current.trend(&trend_forecast, units.trend_threshold());
+                print_current(&location.display_name, current, trend, timezone, &display)?;
+            }
+            Err(err) => eprintln!("Error: {err:#}"),
+        }
+    }
+    Ok(())
+}
+
+/// Print a single current-weather reading: a single pretty-printed attribution-carrying JSON
+/// object with `report`, a JSON line with `json`, or (the default) a formatted single-row table.
+fn print_current(
+    location_name: &str,
+    current: Current,
+    trend: Option<&'static str>,
+    timezone: chrono_tz::Tz,
+    display: &CurrentDisplayOptions,
+) -> anyhow::Result<()> {
+    let &CurrentDisplayOptions {
+        json,
+        report,
+        verbose,
+        style,
+    } = display;
+
+    if report {
+        let location = current.location.clone();
+        println!(
+            "{}",
+            Report::new(current, timezone, location).to_pretty_json()?
+        );
+        return Ok(());
+    }
     if json {
         print_current_json(&current);
         return Ok(());
     }
-    println!("Current weather for {}", location.display_name);
+    println!("Current weather for {location_name}");
     if verbose {
         println!("Grid-cell location: {}", current.location.link());
     }
@@ -254,8 +726,24 @@ async fn do_current(location: &str, json: bool, verbose: bool) -> anyhow::Result
                 current.time.hour() as u8,
             )],
         )
-        .column("Temp", vec![format_temp(current.weather.temp)])
-        .column("Precip", vec![format_precip(current.weather.precip)])
+        .column(
+            "Temp",
+            vec![format_temp(current.weather.temp, current.units)],
+        )
+        .column("Trend", vec![trend.unwrap_or("-").to_string()])
+        .column(
+            "Precip",
+            vec![format_precip(current.weather.precip, current.units)],
+        )
+        .column(
+            "Wind",
+            vec![format_wind(
+                current.weather.wind_speed,
+                current.weather.wind_dir,
+                current.units,
+            )],
+        )
+        .style(style)
         .print();
     Ok(())
 }
@@ -281,465 +769,194 @@ fn print_current_json(current: &Current) {
     println!("{}", serde_json::to_string(&output).unwrap());
 }
 
-#[tokio::main]
-async fn main() -> anyhow::Result<()> {
-    let cli = Cli::parse();
-
-    match cli.command {
-        Command::Forecast {
-            location,
-            dates,
-            models,
-            full,
-            json,
-            verbose,
-        } => do_forecast(&location, &dates, &models, full, json, verbose).await,
-        Command::Current {
-            location,
-            json,
-            verbose,
-        } => do_current(&location, json, verbose).await,
-    }
-}
-
-mod time {
-    use chrono::{
-        DateTime, Datelike, Duration, FixedOffset, NaiveDate, NaiveTime, TimeZone, Weekday,
+/// Handle the `reformat` subcommand: reparse whitespace-aligned tabular text (this tool's own
+/// table output, or third-party command output) into columns, optionally filter and sort the
+/// resulting rows, and re-emit them as CSV or JSON.
+fn do_reformat(
+    file: Option<&PathBuf>,
+    no_header: bool,
+    filter: Option<&str>,
+    sort_by: Option<&str>,
+    format: ReformatFormat,
+) -> anyhow::Result<()> {
+    use anyhow::Context;
+
+    let text = match file {
+        Some(path) => std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?,
+        None => {
+            let mut text = String::new();
+            std::io::stdin()
+                .read_to_string(&mut text)
+                .context("Failed to read stdin")?;
+            text
+        }
     };
-    use chrono_tz::Tz;
-
-    use super::MAX_FORECAST_DAYS;
-
-    #[derive(Debug, Copy, Clone, PartialEq)]
-    pub enum RequestedDate {
-        Today,
-        Tomorrow,
-        RelativeDays(u8),
-        Weekday(Weekday),
-        Absolute(NaiveDate),
-    }
 
-    fn parse_weekday(s: &str) -> Option<Weekday> {
-        match s {
-            "mon" | "monday" => Some(Weekday::Mon),
-            "tue" | "tuesday" => Some(Weekday::Tue),
-            "wed" | "wednesday" => Some(Weekday::Wed),
-            "thu" | "thursday" => Some(Weekday::Thu),
-            "fri" | "friday" => Some(Weekday::Fri),
-            "sat" | "saturday" => Some(Weekday::Sat),
-            "sun" | "sunday" => Some(Weekday::Sun),
-            _ => None,
-        }
-    }
+    let columns = parse_fixed_width(&text, !no_header);
+    let headers: Vec<String> = columns
+        .iter()
+        .enumerate()
+        .map(|(i, col)| col.header.clone().unwrap_or_else(|| format!("column{i}")))
+        .collect();
+    let row_count = columns.first().map_or(0, |col| col.values.len());
+    let mut rows: Vec<Vec<String>> = (0..row_count)
+        .map(|r| columns.iter().map(|col| col.values[r].clone()).collect())
+        .collect();
 
-    fn parse_date(s: &str) -> anyhow::Result<RequestedDate> {
-        use anyhow::Context;
-        let s = s.to_lowercase();
-        match s.as_str() {
-            "today" => Ok(RequestedDate::Today),
-            "tomorrow" => Ok(RequestedDate::Tomorrow),
-            _ => {
-                if let Some(weekday) = parse_weekday(&s) {
-                    Ok(RequestedDate::Weekday(weekday))
-                } else if let Some(days) = s.strip_prefix('+').and_then(|n| n.parse::<u8>().ok()) {
-                    Ok(RequestedDate::RelativeDays(days))
-                } else {
-                    NaiveDate::parse_from_str(&s, "%Y-%m-%d")
-                        .map(RequestedDate::Absolute)
-                        .context(
-                            "dates must be YYYY-MM-DD, +N, weekday name, 'today' or 'tomorrow'",
-                        )
-                }
-            }
-        }
+    if let Some(filter) = filter {
+        let (column, substring) = filter
+            .split_once('=')
+            .context("--filter must be in COLUMN=SUBSTRING form")?;
+        let index = headers
+            .iter()
+            .position(|h| h == column)
+            .with_context(|| format!("Unknown column {column:?}"))?;
+        rows.retain(|row| row[index].contains(substring));
     }
 
-    /// Parse a date string, or return `default` if empty.
-    fn parse_date_or(s: &str, default: RequestedDate) -> anyhow::Result<RequestedDate> {
-        if s.is_empty() {
-            Ok(default)
-        } else {
-            parse_date(s)
-        }
+    if let Some(column) = sort_by {
+        let index = headers
+            .iter()
+            .position(|h| h == column)
+            .with_context(|| format!("Unknown column {column:?}"))?;
+        rows.sort_by(|a, b| a[index].cmp(&b[index]));
     }
 
-    pub fn parse_date_range(s: &str) -> anyhow::Result<(RequestedDate, RequestedDate)> {
-        match s.split_once("..") {
-            Some(("", "")) => anyhow::bail!("empty range '..' not allowed"),
-            Some((left, right)) => {
-                let a = parse_date_or(left, RequestedDate::Today)?;
-                let b = parse_date_or(right, RequestedDate::RelativeDays(MAX_FORECAST_DAYS))?;
-                Ok((a, b))
-            }
-            None => {
-                let d = parse_date(s)?;
-                Ok((d, d))
+    match format {
+        ReformatFormat::Csv => {
+            println!("{}", headers.iter().map(|h| csv_field(h)).join(","));
+            for row in &rows {
+                println!("{}", row.iter().map(|v| csv_field(v)).join(","));
             }
         }
-    }
-
-    fn resolve_date(
-        dt: RequestedDate,
-        relative_to: NaiveDate,
-        weekday_start_at: NaiveDate,
-    ) -> NaiveDate {
-        match dt {
-            RequestedDate::Today => relative_to,
-            RequestedDate::Tomorrow => relative_to + Duration::days(1),
-            RequestedDate::RelativeDays(n) => relative_to + Duration::days(n.into()),
-            RequestedDate::Weekday(wanted) => {
-                let mut date = weekday_start_at;
-                while date.weekday() != wanted {
-                    date += Duration::days(1);
-                }
-                date
+        ReformatFormat::Json => {
+            for row in &rows {
+                let object: serde_json::Map<String, serde_json::Value> = headers
+                    .iter()
+                    .cloned()
+                    .zip(row.iter().cloned().map(serde_json::Value::String))
+                    .collect();
+                println!("{}", serde_json::Value::Object(object));
             }
-            RequestedDate::Absolute(d) => d,
         }
     }
 
-    /// Convert an inclusive date range to a half-open time interval.
-    ///
-    /// Input dates are inclusive (e.g., "mon..wed" means Monday through Wednesday).
-    /// Output is a half-open interval `[start, end)` suitable for filtering hourly data.
-    /// The start time is clamped to `relative_to` to avoid showing past hours.
-    pub fn resolve_time_range(
-        (start_date, mut end_date): (RequestedDate, RequestedDate),
-        timezone: Tz,
-        now: DateTime<FixedOffset>,
-    ) -> (DateTime<FixedOffset>, DateTime<FixedOffset>) {
-        let today = now.date_naive();
-
-        // Open-Meteo provides forecasts at hour starts, so after 23:00 there's no more
-        // data for "today". Since start is clamped to `now`, shift end to "tomorrow" to
-        // avoid an empty forecast. We use 22:55 as the cutoff to account for network
-        // latency.
-        const CUTOFF_TIME: NaiveTime = NaiveTime::from_hms_opt(22, 55, 0).unwrap();
-        if now.time() > CUTOFF_TIME && end_date == RequestedDate::Today {
-            end_date = RequestedDate::Tomorrow;
-        }
-
-        let start_resolved = resolve_date(start_date, today, today);
-        let end_resolved = resolve_date(end_date, today, start_resolved);
-
-        let start_time = timezone
-            .from_local_datetime(&start_resolved.and_time(NaiveTime::MIN))
-            .unwrap()
-            .fixed_offset();
-        let start_time = std::cmp::max(start_time, now);
-
-        let end_resolved = end_resolved + Duration::days(1);
-        let end_time = timezone
-            .from_local_datetime(&end_resolved.and_time(NaiveTime::MIN))
-            .unwrap()
-            .fixed_offset();
+    Ok(())
+}
 
-        (start_time, end_time)
+/// Quote a CSV field if it contains a comma, quote, or newline, doubling any embedded quotes.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
     }
+}
 
-    #[cfg(test)]
-    mod tests {
-        use super::*;
-        use chrono::{TimeZone, Timelike};
-
-        fn make_time(hour: u32, minute: u32) -> DateTime<FixedOffset> {
-            // Use a Wednesday (2025-01-15) as the reference date for weekday tests
-            FixedOffset::east_opt(0)
-                .unwrap()
-                .with_ymd_and_hms(2025, 1, 15, hour, minute, 0)
-                .unwrap()
-        }
-
-        // --- parse_date tests ---
-
-        #[test]
-        fn parse_date_today_tomorrow() {
-            assert_eq!(parse_date("today").unwrap(), RequestedDate::Today);
-            assert_eq!(parse_date("tomorrow").unwrap(), RequestedDate::Tomorrow);
-        }
-
-        #[test]
-        fn parse_date_case_insensitive() {
-            assert_eq!(parse_date("TODAY").unwrap(), RequestedDate::Today);
-            assert_eq!(parse_date("Tomorrow").unwrap(), RequestedDate::Tomorrow);
-            assert_eq!(
-                parse_date("MONDAY").unwrap(),
-                RequestedDate::Weekday(Weekday::Mon)
-            );
-        }
-
-        #[test]
-        fn parse_date_weekdays() {
-            assert_eq!(
-                parse_date("mon").unwrap(),
-                RequestedDate::Weekday(Weekday::Mon)
-            );
-            assert_eq!(
-                parse_date("monday").unwrap(),
-                RequestedDate::Weekday(Weekday::Mon)
-            );
-            assert_eq!(
-                parse_date("tue").unwrap(),
-                RequestedDate::Weekday(Weekday::Tue)
-            );
-            assert_eq!(
-                parse_date("wed").unwrap(),
-                RequestedDate::Weekday(Weekday::Wed)
-            );
-            assert_eq!(
-                parse_date("thu").unwrap(),
-                RequestedDate::Weekday(Weekday::Thu)
-            );
-            assert_eq!(
-                parse_date("fri").unwrap(),
-                RequestedDate::Weekday(Weekday::Fri)
-            );
-            assert_eq!(
-                parse_date("sat").unwrap(),
-                RequestedDate::Weekday(Weekday::Sat)
-            );
-            assert_eq!(
-                parse_date("sun").unwrap(),
-                RequestedDate::Weekday(Weekday::Sun)
-            );
-            assert_eq!(
-                parse_date("sunday").unwrap(),
-                RequestedDate::Weekday(Weekday::Sun)
-            );
-        }
-
-        #[test]
-        fn parse_date_relative_days() {
-            assert_eq!(parse_date("+0").unwrap(), RequestedDate::RelativeDays(0));
-            assert_eq!(parse_date("+1").unwrap(), RequestedDate::RelativeDays(1));
-            assert_eq!(parse_date("+7").unwrap(), RequestedDate::RelativeDays(7));
-            assert_eq!(parse_date("+16").unwrap(), RequestedDate::RelativeDays(16));
-        }
-
-        #[test]
-        fn parse_date_absolute() {
-            assert_eq!(
-                parse_date("2025-01-15").unwrap(),
-                RequestedDate::Absolute(NaiveDate::from_ymd_opt(2025, 1, 15).unwrap())
-            );
-            assert_eq!(
-                parse_date("2024-12-31").unwrap(),
-                RequestedDate::Absolute(NaiveDate::from_ymd_opt(2024, 12, 31).unwrap())
-            );
-        }
-
-        #[test]
-        fn parse_date_invalid() {
-            assert!(parse_date("").is_err());
-            assert!(parse_date("yesterday").is_err());
-            assert!(parse_date("15-01-2025").is_err()); // wrong order
-            assert!(parse_date("2025/01/15").is_err()); // wrong separator
-            assert!(parse_date("invalid").is_err());
-        }
-
-        // --- parse_date_range tests ---
-
-        #[test]
-        fn parse_date_range_single() {
-            let (start, end) = parse_date_range("today").unwrap();
-            assert_eq!(start, RequestedDate::Today);
-            assert_eq!(end, RequestedDate::Today);
-        }
-
-        #[test]
-        fn parse_date_range_range() {
-            let (start, end) = parse_date_range("today..tomorrow").unwrap();
-            assert_eq!(start, RequestedDate::Today);
-            assert_eq!(end, RequestedDate::Tomorrow);
-
-            let (start, end) = parse_date_range("mon..fri").unwrap();
-            assert_eq!(start, RequestedDate::Weekday(Weekday::Mon));
-            assert_eq!(end, RequestedDate::Weekday(Weekday::Fri));
-
-            let (start, end) = parse_date_range("+1..+3").unwrap();
-            assert_eq!(start, RequestedDate::RelativeDays(1));
-            assert_eq!(end, RequestedDate::RelativeDays(3));
-        }
-
-        #[test]
-        fn parse_date_range_open_ended() {
-            // ..fri means today..fri
-            let (start, end) = parse_date_range("..fri").unwrap();
-            assert_eq!(start, RequestedDate::Today);
-            assert_eq!(end, RequestedDate::Weekday(Weekday::Fri));
-
-            // mon.. means mon..+16
-            let (start, end) = parse_date_range("mon..").unwrap();
-            assert_eq!(start, RequestedDate::Weekday(Weekday::Mon));
-            assert_eq!(end, RequestedDate::RelativeDays(MAX_FORECAST_DAYS));
-
-            // just .. is forbidden
-            assert!(parse_date_range("..").is_err());
-        }
-
-        #[test]
-        fn parse_date_range_invalid() {
-            assert!(parse_date_range("invalid..today").is_err());
-            assert!(parse_date_range("today..invalid").is_err());
-        }
-
-        // --- resolve_time_range tests ---
-
-        /// Test helper that parses a date range string and resolves it in UTC.
-        fn test_resolve(
-            dates: &str,
-            relative_to: DateTime<FixedOffset>,
-        ) -> (DateTime<FixedOffset>, DateTime<FixedOffset>) {
-            let date_range = parse_date_range(dates).unwrap();
-            resolve_time_range(date_range, chrono_tz::UTC, relative_to)
-        }
-
-        #[test]
-        fn resolve_time_range_today_before_cutoff() {
-            let relative_to = make_time(12, 0); // noon
-            let (start, end) = test_resolve("today", relative_to);
-            // Start should be clamped to relative_to (noon)
-            assert_eq!(start.hour(), 12);
-            // End should be midnight of the next day
-            assert_eq!(
-                end.date_naive(),
-                NaiveDate::from_ymd_opt(2025, 1, 16).unwrap()
-            );
-            assert_eq!(end.hour(), 0);
-        }
-
-        #[test]
-        fn resolve_time_range_today_after_cutoff() {
-            let now = make_time(23, 0); // after 22:55
-            let (start, end) = test_resolve("today", now);
-            // Start is clamped to now (23:00 today)
-            assert_eq!(
-                start.date_naive(),
-                NaiveDate::from_ymd_opt(2025, 1, 15).unwrap()
-            );
-            assert_eq!(start.hour(), 23);
-            // End shifts to tomorrow, so end time is midnight day-after-tomorrow
-            assert_eq!(
-                end.date_naive(),
-                NaiveDate::from_ymd_opt(2025, 1, 17).unwrap()
-            );
-        }
-
-        #[test]
-        fn resolve_time_range_at_cutoff_boundary() {
-            // Exactly at 22:55 should NOT trigger the end shift (we use >)
-            let (start, end) = test_resolve("today", make_time(22, 55));
-            assert_eq!(
-                start.date_naive(),
-                NaiveDate::from_ymd_opt(2025, 1, 15).unwrap()
-            );
-            assert_eq!(
-                end.date_naive(),
-                NaiveDate::from_ymd_opt(2025, 1, 16).unwrap()
-            );
-
-            // One minute later should trigger the end shift
-            let (start, end) = test_resolve("today", make_time(22, 56));
-            assert_eq!(
-                start.date_naive(),
-                NaiveDate::from_ymd_opt(2025, 1, 15).unwrap()
-            );
-            assert_eq!(
-                end.date_naive(),
-                NaiveDate::from_ymd_opt(2025, 1, 17).unwrap()
-            );
-        }
-
-        #[test]
-        fn resolve_time_range_relative_days() {
-            let (start, end) = test_resolve("+2..+3", make_time(10, 0));
-            // +2 from 2025-01-15 is 2025-01-17
-            assert_eq!(
-                start.date_naive(),
-                NaiveDate::from_ymd_opt(2025, 1, 17).unwrap()
-            );
-            // +3 from 2025-01-15 is 2025-01-18, end is midnight of next day
-            assert_eq!(
-                end.date_naive(),
-                NaiveDate::from_ymd_opt(2025, 1, 19).unwrap()
-            );
-        }
-
-        #[test]
-        fn resolve_time_range_weekday() {
-            // Reference is Wednesday 2025-01-15
-            let (start, end) = test_resolve("fri..sun", make_time(10, 0));
-            // Friday after Wednesday 2025-01-15 is 2025-01-17
-            assert_eq!(
-                start.date_naive(),
-                NaiveDate::from_ymd_opt(2025, 1, 17).unwrap()
-            );
-            // Sunday after Friday is 2025-01-19, end is midnight of next day
-            assert_eq!(
-                end.date_naive(),
-                NaiveDate::from_ymd_opt(2025, 1, 20).unwrap()
-            );
-        }
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
 
-        #[test]
-        fn resolve_time_range_absolute_ignores_cutoff() {
-            let relative_to = make_time(23, 30); // after cutoff
-            let (start, end) = test_resolve("2025-01-15", relative_to);
-            // Absolute dates should not be affected by the cutoff
-            // But start is still clamped to relative_to
-            assert_eq!(start.hour(), 23);
-            assert_eq!(start.minute(), 30);
-            assert_eq!(
-                end.date_naive(),
-                NaiveDate::from_ymd_opt(2025, 1, 16).unwrap()
-            );
+    match cli.command {
+        Command::Forecast {
+            location,
+            locations,
+            dates,
+            models,
+            full,
+            json,
+            report,
+            verbose,
+            timezone,
+            units,
+            days,
+            air_quality,
+            no_wrap,
+            style,
+            bucket_hours,
+        } if locations.is_empty() => {
+            let options = ForecastOptions {
+                models: &models,
+                full,
+                json,
+                report,
+                verbose,
+                timezone: timezone.as_deref(),
+                units,
+                days,
+                air_quality,
+                no_wrap,
+                style,
+                bucket_hours,
+            };
+            do_forecast(location.as_deref(), &dates, &options).await
         }
-
-        #[test]
-        fn resolve_time_range_start_clamped_to_relative_to() {
-            // If relative_to is in the afternoon, start should be clamped
-            let (start, _) = test_resolve("today", make_time(15, 30));
-            assert_eq!(start.hour(), 15);
-            assert_eq!(start.minute(), 30);
+        Command::Forecast {
+            location,
+            locations,
+            dates,
+            models,
+            full,
+            json,
+            report,
+            verbose,
+            timezone,
+            units,
+            days,
+            air_quality,
+            no_wrap,
+            style,
+            bucket_hours,
+        } => {
+            let options = ForecastOptions {
+                models: &models,
+                full,
+                json,
+                report,
+                verbose,
+                timezone: timezone.as_deref(),
+                units,
+                days,
+                air_quality,
+                no_wrap,
+                style,
+                bucket_hours,
+            };
+            do_forecast_batch(location.as_deref(), &locations, &dates, &options).await
         }
-
-        #[test]
-        fn resolve_time_range_respects_timezone() {
-            // 10:00 UTC on 2025-01-15
-            let relative_to = FixedOffset::east_opt(0)
-                .unwrap()
-                .with_ymd_and_hms(2025, 1, 15, 10, 0, 0)
-                .unwrap();
-
-            // In UTC, "tomorrow" starts at 2025-01-16 00:00:00 UTC
-            let (start_utc, _) = resolve_time_range(
-                parse_date_range("tomorrow").unwrap(),
-                chrono_tz::UTC,
-                relative_to,
-            );
-            assert_eq!(
-                start_utc.date_naive(),
-                NaiveDate::from_ymd_opt(2025, 1, 16).unwrap()
-            );
-            assert_eq!(start_utc.hour(), 0);
-            assert_eq!(start_utc.offset().local_minus_utc(), 0);
-
-            // In Europe/Zagreb (UTC+1 in winter), "tomorrow" starts at 2025-01-16 00:00:00
-            // local, which is 2025-01-15 23:00:00 UTC
-            let (start_zagreb, _) = resolve_time_range(
-                parse_date_range("tomorrow").unwrap(),
-                chrono_tz::Europe::Zagreb,
-                relative_to,
-            );
-            assert_eq!(
-                start_zagreb.date_naive(),
-                NaiveDate::from_ymd_opt(2025, 1, 16).unwrap()
-            );
-            assert_eq!(start_zagreb.hour(), 0);
-            assert_eq!(start_zagreb.offset().local_minus_utc(), 3600); // UTC+1
-
-            // The Zagreb time should be 1 hour earlier in absolute terms
-            assert_eq!(start_zagreb.timestamp(), start_utc.timestamp() - 3600);
+        Command::Current {
+            location,
+            json,
+            report,
+            verbose,
+            units,
+            style,
+            watch,
+        } => {
+            do_current(
+                location.as_deref(),
+                json,
+                report,
+                verbose,
+                units,
+                style,
+                watch,
+            )
+            .await
         }
+        Command::Reformat {
+            file,
+            no_header,
+            filter,
+            sort_by,
+            format,
+        } => do_reformat(
+            file.as_ref(),
+            no_header,
+            filter.as_deref(),
+            sort_by.as_deref(),
+            format,
+        ),
     }
 }
@@ -1,11 +1,16 @@
+use std::collections::HashMap;
+
 use chrono::{DateTime, FixedOffset, NaiveDate, Timelike};
+use clap::ValueEnum;
+use serde::Serialize;
 use unicode_width::UnicodeWidthStr;
 
 /// Maximum forecast days supported by Open-Meteo.
 pub const MAX_FORECAST_DAYS: u8 = 16;
 
 /// WMO weather code with display formatting.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(transparent)]
 pub struct WmoCode(pub u8);
 
 impl WmoCode {
@@ -72,32 +77,206 @@ pub fn format_wmo_symbol(code: Option<WmoCode>, hour: u8) -> String {
 }
 
 /// Format an optional temperature value.
-pub fn format_temp(temp: Option<f64>) -> String {
+///
+/// `download_forecast`/`download_current` already ask Open-Meteo to convert to `units` server
+/// side, so `temp` arrives in the right scale; this only picks the matching suffix.
+pub fn format_temp(temp: Option<f64>, units: Units) -> String {
     match temp {
         // as i32 so -0.1 doesn't show up as -0
-        Some(t) => format!("{}°", t.round() as i32),
+        Some(t) => format!("{}°{}", t.round() as i32, units.temperature_symbol()),
         None => "-".to_string(),
     }
 }
 
 /// Format an optional precipitation value.
-pub fn format_precip(precip: Option<f64>) -> String {
-    match precip {
-        Some(0.0) => String::new(),
-        Some(p) if p < 5. => format!("{p:.1}mm"),
-        Some(p) => format!("{p:.0}mm"),
-        None => "-".to_string(),
+///
+/// As with [`format_temp`], `precip` already arrives in `units`' native scale; this picks the
+/// matching suffix and unit-appropriate rounding (imperial uses inches, an order of magnitude
+/// smaller than millimeters, so its "near zero" threshold and decimal places differ).
+pub fn format_precip(precip: Option<f64>, units: Units) -> String {
+    match units {
+        Units::Metric => match precip {
+            Some(0.0) => String::new(),
+            Some(p) if p < 5. => format!("{p:.1}mm"),
+            Some(p) => format!("{p:.0}mm"),
+            None => "-".to_string(),
+        },
+        Units::Imperial => match precip {
+            Some(0.0) => String::new(),
+            Some(p) if p < 0.2 => format!("{p:.2}in"),
+            Some(p) => format!("{p:.1}in"),
+            None => "-".to_string(),
+        },
+    }
+}
+
+/// Compass arrows for each of the 8 wind-direction buckets, indexed by
+/// `((deg + 22.5) / 45.0) as usize % 8`.
+///
+/// Arrows point where the wind is blowing *to*, opposite the meteorological "from" bearing
+/// Open-Meteo reports (0 = wind from the north), so a north wind renders as "↓".
+const WIND_ARROWS: [&str; 8] = ["↓", "↙", "←", "↖", "↑", "↗", "→", "↘"];
+
+/// Format an optional wind speed and direction as speed-with-unit plus a compass arrow.
+pub fn format_wind(speed: Option<f64>, dir: Option<f64>, units: Units) -> String {
+    match (speed, dir) {
+        (Some(speed), Some(dir)) => {
+            let arrow = WIND_ARROWS[((dir + 22.5) / 45.0) as usize % 8];
+            format!("{:.0}{} {}", speed, units.wind_speed_symbol(), arrow)
+        }
+        (Some(speed), None) => format!("{:.0}{}", speed, units.wind_speed_symbol()),
+        (None, _) => "-".to_string(),
     }
 }
 
-#[derive(Debug, Clone)]
+/// A single hour of weather data from Open-Meteo's forecast API.
+///
+/// `temp`, `precip`, and `wind_speed` are in whatever `Units` the enclosing `Forecast` was
+/// downloaded with, not fixed metric — Open-Meteo converts server-side per `Units::temperature_unit`
+/// etc., so a `WeatherPoint` can't be interpreted without knowing that `Units`.
+#[derive(Debug, Clone, Serialize)]
 pub struct WeatherPoint {
     pub temp: Option<f64>,
     pub precip: Option<f64>,
     pub code: Option<WmoCode>,
+    pub apparent_temp: Option<f64>,
+    pub humidity: Option<f64>,
+    pub wind_speed: Option<f64>,
+    /// Wind direction in meteorological degrees (0-360, 0 = north, clockwise).
+    pub wind_dir: Option<f64>,
+    pub pressure: Option<f64>,
+    /// Probability of precipitation, as a percentage (0-100).
+    pub precipitation_probability: Option<f64>,
+}
+
+/// A single hour of air-quality data from Open-Meteo's air-quality API.
+#[derive(Debug, Clone, Serialize)]
+pub struct AirQualityPoint {
+    /// Fine particulate matter, in µg/m³.
+    pub pm2_5: Option<f64>,
+    /// European Air Quality Index (0 good, 100+ extremely poor).
+    pub aqi: Option<u32>,
+    /// UV index (0 low, 11+ extreme).
+    pub uv_index: Option<f64>,
+}
+
+/// Classify a European AQI value into a coarse, human-facing band.
+///
+/// Thresholds follow Open-Meteo's European AQI scale, collapsed to the three bands
+/// callers care about at a glance.
+pub fn aqi_band(aqi: u32) -> &'static str {
+    match aqi {
+        0..=40 => "good",
+        41..=80 => "moderate",
+        _ => "unhealthy",
+    }
+}
+
+/// ANSI color for `aqi_band`'s result, used to annotate `format_aqi`'s output.
+fn aqi_band_color(aqi: u32) -> &'static str {
+    match aqi_band(aqi) {
+        "good" => "\x1b[32m",      // green
+        "moderate" => "\x1b[33m",  // yellow
+        _ => "\x1b[31m",           // red
+    }
+}
+
+/// Format an optional European AQI value, colored by its band (see `aqi_band`).
+pub fn format_aqi(aqi: Option<u32>) -> String {
+    match aqi {
+        Some(aqi) => format!("{}{}\x1b[0m", aqi_band_color(aqi), aqi),
+        None => "-".to_string(),
+    }
+}
+
+/// Unit system for temperature, precipitation, and wind speed.
+///
+/// Open-Meteo reports metric values by default; selecting `Imperial` asks the API to
+/// convert server-side so downstream formatting doesn't need to know the source unit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Units {
+    Metric,
+    Imperial,
+}
+
+impl std::fmt::Display for Units {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.to_possible_value()
+            .expect("no values are skipped")
+            .get_name()
+            .fmt(f)
+    }
 }
 
-#[derive(Debug, Clone)]
+impl Units {
+    /// Value for Open-Meteo's `temperature_unit` query parameter.
+    pub fn temperature_unit(self) -> &'static str {
+        match self {
+            Units::Metric => "celsius",
+            Units::Imperial => "fahrenheit",
+        }
+    }
+
+    /// Value for Open-Meteo's `precipitation_unit` query parameter.
+    pub fn precipitation_unit(self) -> &'static str {
+        match self {
+            Units::Metric => "mm",
+            Units::Imperial => "inch",
+        }
+    }
+
+    /// Value for Open-Meteo's `wind_speed_unit` query parameter.
+    pub fn wind_speed_unit(self) -> &'static str {
+        match self {
+            Units::Metric => "kmh",
+            Units::Imperial => "mph",
+        }
+    }
+
+    /// Degree-symbol suffix used by `format_temp`.
+    pub fn temperature_symbol(self) -> &'static str {
+        match self {
+            Units::Metric => "C",
+            Units::Imperial => "F",
+        }
+    }
+
+    /// Speed-unit suffix used by `format_wind`.
+    pub fn wind_speed_symbol(self) -> &'static str {
+        match self {
+            Units::Metric => "km/h",
+            Units::Imperial => "mph",
+        }
+    }
+
+    /// Default "steady" threshold for `Current::trend`, in this unit system's degrees.
+    pub fn trend_threshold(self) -> f64 {
+        match self {
+            Units::Metric => 1.0,
+            Units::Imperial => 2.0,
+        }
+    }
+}
+
+/// Average a list of wind bearings (degrees) using a circular/vector mean: sum the unit
+/// vectors of each bearing and take `atan2` of the sums, normalizing back to 0-360°.
+///
+/// A plain arithmetic mean is wrong for bearings (the mean of 350° and 10° should be 0°,
+/// not 180°), so gusting winds that cross due north don't collapse to a nonsensical value.
+fn circular_mean_degrees(degrees: &[f64]) -> Option<f64> {
+    if degrees.is_empty() {
+        return None;
+    }
+    let (sin_sum, cos_sum) = degrees.iter().fold((0.0, 0.0), |(sin_acc, cos_acc), d| {
+        let rad = d.to_radians();
+        (sin_acc + rad.sin(), cos_acc + rad.cos())
+    });
+    let mean = sin_sum.atan2(cos_sum).to_degrees();
+    Some((mean + 360.0) % 360.0)
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct Coord {
     pub latitude: f64,
     pub longitude: f64,
@@ -112,47 +291,103 @@ impl Coord {
     }
 }
 
-#[derive(Debug)]
+/// Multi-model weather forecast data from Open-Meteo's forecast API.
+#[derive(Debug, Serialize)]
 pub struct Forecast {
     pub times: Vec<DateTime<FixedOffset>>,
+    /// Each model's points, in the scale of `units` below; see `WeatherPoint`'s doc comment.
     pub by_model: Vec<(String, Vec<WeatherPoint>)>,
     pub timezone: chrono_tz::Tz,
     pub location: Coord,
+    pub units: Units,
+    /// Air-quality data aligned 1:1 with `times`, when requested; `None` unless the caller
+    /// fetched it separately from Open-Meteo's air-quality API and attached it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub air_quality: Option<Vec<AirQualityPoint>>,
+}
+
+/// Configuration for `Forecast::compact_with`: how many days stay fully hourly, and the
+/// bucket size used to group the remainder.
+#[derive(Debug, Clone, Copy)]
+pub struct CompactOptions {
+    /// Number of days, starting from the reference "today", kept at hourly resolution.
+    pub hourly_days: u8,
+    /// Bucket size in hours for days beyond `hourly_days`. Must evenly divide 24.
+    pub bucket_hours: u8,
+}
+
+impl Default for CompactOptions {
+    /// The long-standing default: today stays hourly, other days use 3-hour buckets.
+    fn default() -> Self {
+        CompactOptions {
+            hourly_days: 1,
+            bucket_hours: 3,
+        }
+    }
 }
 
 impl Forecast {
-    /// Compact forecast data into a smaller number of points: keep hourly for today, use
-    /// 3-hour intervals for other days.
-    ///
-    /// For compacted intervals, temperature is averaged, precipitation is summed, and the
-    /// most significant WMO weather code is selected (e.g., rain takes precedence over
-    /// sun).
+    /// Compact forecast data into a smaller number of points using the default resolution:
+    /// hourly for today, 3-hour buckets for other days. See `compact_with` to configure this.
     pub fn compact(&mut self, today: NaiveDate) {
+        self.compact_with(today, CompactOptions::default())
+            .expect("the default bucket_hours always evenly divides 24");
+    }
+
+    /// Compact forecast data into a smaller number of points: keep hourly resolution for the
+    /// first `options.hourly_days` days starting at `today`, and group the remainder into
+    /// `options.bucket_hours`-hour buckets.
+    ///
+    /// For compacted intervals, temperature, apparent temperature, humidity, pressure, and
+    /// wind speed are averaged, precipitation is summed, precipitation probability takes the
+    /// bucket maximum, wind direction is averaged circularly, and the most significant WMO
+    /// weather code is selected (e.g., rain takes precedence over sun). Air quality (PM2.5,
+    /// AQI, UV index), if present, takes the bucket maximum rather than the average, since
+    /// peak exposure over the interval matters more than the mean.
+    ///
+    /// Returns an error rather than panicking if `options.bucket_hours` doesn't evenly divide 24.
+    pub fn compact_with(
+        &mut self,
+        today: NaiveDate,
+        options: CompactOptions,
+    ) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            24 % options.bucket_hours as u32 == 0,
+            "bucket_hours ({}) must evenly divide 24",
+            options.bucket_hours
+        );
+
         let mut new_times = Vec::new();
         let mut new_by_model: Vec<(String, Vec<WeatherPoint>)> = self
             .by_model
             .iter()
             .map(|(name, _)| (name.clone(), Vec::new()))
             .collect();
+        let mut new_air_quality: Option<Vec<AirQualityPoint>> =
+            self.air_quality.as_ref().map(|_| Vec::new());
 
         let mut i = 0;
         while i < self.times.len() {
             let time = self.times[i];
             let date = time.date_naive();
 
-            if date == today {
-                // Keep hourly for today
+            if (date - today).num_days() < options.hourly_days as i64 {
+                // Keep hourly for the configured leading days
                 new_times.push(time);
                 for (model_idx, (_, weather)) in self.by_model.iter().enumerate() {
                     new_by_model[model_idx].1.push(weather[i].clone());
                 }
+                if let (Some(new_aq), Some(aq)) = (&mut new_air_quality, &self.air_quality) {
+                    new_aq.push(aq[i].clone());
+                }
                 i += 1;
             } else {
-                // Compress to 3-hour intervals for other days
-                let bucket_start_hour = time.hour() / 3 * 3;
-                let bucket_end_hour = bucket_start_hour + 3;
+                // Compress to the configured bucket size for other days
+                let bucket_hours = options.bucket_hours as u32;
+                let bucket_start_hour = time.hour() / bucket_hours * bucket_hours;
+                let bucket_end_hour = bucket_start_hour + bucket_hours;
 
-                // Find all hours in this 3-hour bucket
+                // Find all hours in this bucket
                 let mut bucket_indices = vec![i];
                 let mut j = i + 1;
                 while j < self.times.len() {
@@ -195,10 +430,63 @@ impl Forecast {
                         .filter_map(|p| p.code)
                         .max_by_key(|code| code.severity());
 
+                    // Average apparent temperature, humidity, pressure, and wind speed
+                    let avg = |values: Vec<f64>| -> Option<f64> {
+                        if values.is_empty() {
+                            None
+                        } else {
+                            Some(values.iter().sum::<f64>() / values.len() as f64)
+                        }
+                    };
+                    let avg_apparent_temp =
+                        avg(points.iter().filter_map(|p| p.apparent_temp).collect());
+                    let avg_humidity = avg(points.iter().filter_map(|p| p.humidity).collect());
+                    let avg_pressure = avg(points.iter().filter_map(|p| p.pressure).collect());
+                    let avg_wind_speed =
+                        avg(points.iter().filter_map(|p| p.wind_speed).collect());
+
+                    // Circular mean for wind direction: see `circular_mean_degrees`.
+                    let avg_wind_dir = circular_mean_degrees(
+                        &points.iter().filter_map(|p| p.wind_dir).collect::<Vec<_>>(),
+                    );
+
+                    // Worst-case (maximum) chance of precipitation over the bucket
+                    let max_precip_probability = points
+                        .iter()
+                        .filter_map(|p| p.precipitation_probability)
+                        .max_by(|a, b| a.total_cmp(b));
+
                     new_by_model[model_idx].1.push(WeatherPoint {
                         temp: avg_temp,
                         precip: sum_precip,
                         code: most_significant_code,
+                        apparent_temp: avg_apparent_temp,
+                        humidity: avg_humidity,
+                        wind_speed: avg_wind_speed,
+                        wind_dir: avg_wind_dir,
+                        pressure: avg_pressure,
+                        precipitation_probability: max_precip_probability,
+                    });
+                }
+
+                // Worst-case (maximum) air quality over the bucket: peak exposure matters more
+                // than the average.
+                if let (Some(new_aq), Some(aq)) = (&mut new_air_quality, &self.air_quality) {
+                    let points: Vec<&AirQualityPoint> =
+                        bucket_indices.iter().map(|&idx| &aq[idx]).collect();
+                    let max_pm2_5 = points
+                        .iter()
+                        .filter_map(|p| p.pm2_5)
+                        .max_by(|a, b| a.total_cmp(b));
+                    let max_aqi = points.iter().filter_map(|p| p.aqi).max();
+                    let max_uv_index = points
+                        .iter()
+                        .filter_map(|p| p.uv_index)
+                        .max_by(|a, b| a.total_cmp(b));
+                    new_aq.push(AirQualityPoint {
+                        pm2_5: max_pm2_5,
+                        aqi: max_aqi,
+                        uv_index: max_uv_index,
                     });
                 }
 
@@ -208,12 +496,263 @@ impl Forecast {
 
         self.times = new_times;
         self.by_model = new_by_model;
+        self.air_quality = new_air_quality;
+        Ok(())
+    }
+}
+
+/// Merge a historical (`fetch::download_historical`) and a forecast `Forecast` into one
+/// continuous series keyed on the union of both time axes, for a seamless past-through-future
+/// view of a date range that starts before today.
+///
+/// Where the two don't overlap, missing points are filled in as `None` rather than dropped, so
+/// every model's column lines up with the merged `times`.
+pub fn stitch_historical(historical: Forecast, forecast: Forecast) -> Forecast {
+    let mut times: Vec<DateTime<FixedOffset>> = historical
+        .times
+        .iter()
+        .chain(&forecast.times)
+        .copied()
+        .collect();
+    times.sort();
+    times.dedup();
+
+    let align =
+        |src_times: &[DateTime<FixedOffset>], points: &[WeatherPoint]| -> Vec<WeatherPoint> {
+            let by_time: HashMap<DateTime<FixedOffset>, &WeatherPoint> =
+                src_times.iter().copied().zip(points).collect();
+            times
+                .iter()
+                .map(|t| {
+                    by_time.get(t).copied().cloned().unwrap_or(WeatherPoint {
+                        temp: None,
+                        precip: None,
+                        code: None,
+                        apparent_temp: None,
+                        humidity: None,
+                        wind_speed: None,
+                        wind_dir: None,
+                        pressure: None,
+                        precipitation_probability: None,
+                    })
+                })
+                .collect()
+        };
+
+    let mut by_model = vec![(
+        historical.by_model[0].0.clone(),
+        align(&historical.times, &historical.by_model[0].1),
+    )];
+    for (model, points) in &forecast.by_model {
+        by_model.push((model.clone(), align(&forecast.times, points)));
+    }
+
+    Forecast {
+        times,
+        by_model,
+        timezone: forecast.timezone,
+        location: forecast.location,
+        units: forecast.units,
+        air_quality: forecast.air_quality,
     }
 }
 
-#[derive(Debug)]
+/// A single current-weather reading from Open-Meteo's forecast API.
+#[derive(Debug, Serialize)]
 pub struct Current {
+    /// Already in the scale of `units` below; see `WeatherPoint`'s doc comment.
     pub weather: WeatherPoint,
     pub time: DateTime<FixedOffset>,
     pub location: Coord,
+    pub units: Units,
+}
+
+/// Glyph for a temperature change of `diff` degrees, treating anything within `threshold`
+/// (inclusive) as steady rather than rising or falling.
+fn trend_glyph(diff: f64, threshold: f64) -> &'static str {
+    if diff > threshold {
+        "↑"
+    } else if diff < -threshold {
+        "↓"
+    } else {
+        "→"
+    }
+}
+
+impl Current {
+    /// Compare this reading's temperature to the next upcoming point in `forecast` (the first
+    /// forecast time after `self.time`, from its first model) and return a rising/steady/
+    /// falling glyph, so a compact status line can print e.g. `12° ↑ 15°`.
+    ///
+    /// `threshold` is the minimum change (in degrees, same unit as `self.units`) before the
+    /// trend is reported as rising or falling rather than steady. Returns `None` if either
+    /// temperature is missing or there's no forecast point after `self.time`.
+    pub fn trend(&self, forecast: &Forecast, threshold: f64) -> Option<&'static str> {
+        let current_temp = self.weather.temp?;
+        let (_, points) = forecast.by_model.first()?;
+        let idx = forecast.times.iter().position(|&t| t > self.time)?;
+        let next_temp = points.get(idx)?.temp?;
+        Some(trend_glyph(next_temp - current_temp, threshold))
+    }
+}
+
+/// A machine-readable report bundling weather data with its source attribution.
+///
+/// Many downstream tools require a credit line alongside any Open-Meteo-derived data; wrapping
+/// a `Forecast` or `Current` in a `Report` keeps that attribution attached through serialization.
+#[derive(Debug, Serialize)]
+pub struct Report<T> {
+    pub data_source: String,
+    pub timezone: chrono_tz::Tz,
+    pub location: Coord,
+    pub data: T,
+}
+
+impl<T> Report<T> {
+    pub fn new(data: T, timezone: chrono_tz::Tz, location: Coord) -> Self {
+        Report {
+            data_source: "Open-Meteo (https://open-meteo.com/)".to_string(),
+            timezone,
+            location,
+            data,
+        }
+    }
+}
+
+impl<T: Serialize> Report<T> {
+    /// Serialize this report as pretty-printed JSON.
+    pub fn to_pretty_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_temp_negative_near_zero_does_not_show_negative_zero() {
+        assert_eq!(format_temp(Some(-0.1), Units::Metric), "0°C");
+        assert_eq!(format_temp(Some(-0.1), Units::Imperial), "0°F");
+    }
+
+    #[test]
+    fn format_temp_uses_matching_unit_symbol() {
+        assert_eq!(format_temp(Some(20.4), Units::Metric), "20°C");
+        assert_eq!(format_temp(Some(68.7), Units::Imperial), "69°F");
+        assert_eq!(format_temp(None, Units::Metric), "-");
+    }
+
+    #[test]
+    fn format_precip_metric_threshold() {
+        assert_eq!(format_precip(Some(0.0), Units::Metric), "");
+        assert_eq!(format_precip(Some(4.9), Units::Metric), "4.9mm");
+        assert_eq!(format_precip(Some(5.0), Units::Metric), "5mm");
+    }
+
+    #[test]
+    fn format_precip_imperial_threshold() {
+        assert_eq!(format_precip(Some(0.0), Units::Imperial), "");
+        assert_eq!(format_precip(Some(0.19), Units::Imperial), "0.19in");
+        assert_eq!(format_precip(Some(0.2), Units::Imperial), "0.2in");
+        assert_eq!(format_precip(None, Units::Imperial), "-");
+    }
+
+    #[test]
+    fn format_wind_picks_arrow_by_bucket() {
+        assert_eq!(format_wind(Some(10.0), Some(0.0), Units::Metric), "10km/h ↓");
+        assert_eq!(format_wind(Some(10.0), Some(90.0), Units::Metric), "10km/h ←");
+        assert_eq!(format_wind(Some(10.0), Some(180.0), Units::Metric), "10km/h ↑");
+        assert_eq!(format_wind(Some(10.0), Some(270.0), Units::Metric), "10km/h →");
+        // Wraps back to the first bucket just past 360 - 22.5.
+        assert_eq!(format_wind(Some(10.0), Some(359.0), Units::Metric), "10km/h ↓");
+    }
+
+    #[test]
+    fn format_wind_missing_values() {
+        assert_eq!(format_wind(Some(5.0), None, Units::Imperial), "5mph");
+        assert_eq!(format_wind(None, Some(90.0), Units::Metric), "-");
+        assert_eq!(format_wind(None, None, Units::Metric), "-");
+    }
+
+    fn weather_point_with_temp(temp: Option<f64>) -> WeatherPoint {
+        WeatherPoint {
+            temp,
+            precip: None,
+            code: None,
+            apparent_temp: None,
+            humidity: None,
+            wind_speed: None,
+            wind_dir: None,
+            pressure: None,
+            precipitation_probability: None,
+        }
+    }
+
+    fn current_at(time: DateTime<FixedOffset>, temp: Option<f64>) -> Current {
+        Current {
+            weather: weather_point_with_temp(temp),
+            time,
+            location: Coord {
+                latitude: 0.0,
+                longitude: 0.0,
+            },
+            units: Units::Metric,
+        }
+    }
+
+    fn forecast_with_next_temp(time: DateTime<FixedOffset>, temp: Option<f64>) -> Forecast {
+        Forecast {
+            times: vec![time],
+            by_model: vec![("model".to_string(), vec![weather_point_with_temp(temp)])],
+            timezone: chrono_tz::UTC,
+            location: Coord {
+                latitude: 0.0,
+                longitude: 0.0,
+            },
+            units: Units::Metric,
+            air_quality: None,
+        }
+    }
+
+    #[test]
+    fn trend_rising_above_threshold() {
+        let now = DateTime::parse_from_rfc3339("2024-01-01T00:00:00+00:00").unwrap();
+        let later = DateTime::parse_from_rfc3339("2024-01-01T01:00:00+00:00").unwrap();
+        let current = current_at(now, Some(12.0));
+        let forecast = forecast_with_next_temp(later, Some(15.0));
+        assert_eq!(current.trend(&forecast, 1.0), Some("↑"));
+    }
+
+    #[test]
+    fn trend_falling_below_threshold() {
+        let now = DateTime::parse_from_rfc3339("2024-01-01T00:00:00+00:00").unwrap();
+        let later = DateTime::parse_from_rfc3339("2024-01-01T01:00:00+00:00").unwrap();
+        let current = current_at(now, Some(15.0));
+        let forecast = forecast_with_next_temp(later, Some(12.0));
+        assert_eq!(current.trend(&forecast, 1.0), Some("↓"));
+    }
+
+    #[test]
+    fn trend_steady_within_threshold() {
+        let now = DateTime::parse_from_rfc3339("2024-01-01T00:00:00+00:00").unwrap();
+        let later = DateTime::parse_from_rfc3339("2024-01-01T01:00:00+00:00").unwrap();
+        let current = current_at(now, Some(12.0));
+        let forecast = forecast_with_next_temp(later, Some(12.5));
+        assert_eq!(current.trend(&forecast, 1.0), Some("→"));
+    }
+
+    #[test]
+    fn trend_none_when_temps_missing() {
+        let now = DateTime::parse_from_rfc3339("2024-01-01T00:00:00+00:00").unwrap();
+        let later = DateTime::parse_from_rfc3339("2024-01-01T01:00:00+00:00").unwrap();
+        assert_eq!(
+            current_at(now, None).trend(&forecast_with_next_temp(later, Some(12.0)), 1.0),
+            None
+        );
+        assert_eq!(
+            current_at(now, Some(12.0)).trend(&forecast_with_next_temp(later, None), 1.0),
+            None
+        );
+    }
 }
@@ -1,65 +1,332 @@
 use std::collections::HashMap;
+use std::time::Duration;
 
 use anyhow::{bail, Context};
-use chrono::{NaiveDateTime, TimeZone};
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, TimeZone};
+use itertools::izip;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
 
-use crate::data::{Coord, Current, Forecast, WeatherPoint, WmoCode, MAX_FORECAST_DAYS};
+use crate::data::{AirQualityPoint, Coord, Current, Forecast, Units, WeatherPoint, WmoCode};
+
+/// Open-Meteo `hourly` field list shared by the single- and multi-location forecast requests.
+const HOURLY_FIELDS: &str = "temperature_2m,precipitation,weather_code,apparent_temperature,\
+                              relative_humidity_2m,wind_speed_10m,wind_direction_10m,surface_pressure,\
+                              precipitation_probability";
+
+#[derive(Debug, Deserialize)]
+struct ForecastResponse {
+    latitude: f64,
+    longitude: f64,
+    timezone: chrono_tz::Tz,
+    hourly: HourlyData,
+}
+
+#[derive(Debug, Deserialize)]
+struct HourlyData {
+    time: Vec<String>,
+    #[serde(flatten)]
+    data: HashMap<String, Vec<serde_json::Value>>,
+}
+
+impl HourlyData {
+    /// Remove `key` from data and deserialize its JSON array into `Vec<Option<T>>`.
+    fn take_field_array<T: DeserializeOwned>(&mut self, key: &str) -> Vec<Option<T>> {
+        self.data
+            .remove(key)
+            .and_then(|v| serde_json::from_value(serde_json::Value::Array(v)).ok())
+            .unwrap_or_default()
+    }
+}
+
+/// Convert a single Open-Meteo forecast response into a `Forecast`, splitting the per-model
+/// hourly fields out of `HourlyData` via `take_field_array`.
+fn forecast_from_response(
+    mut data: ForecastResponse,
+    models: &[&str],
+    units: Units,
+) -> Forecast {
+    let times = data
+        .hourly
+        .time
+        .iter()
+        .map(|t| {
+            let naive =
+                NaiveDateTime::parse_from_str(t, "%Y-%m-%dT%H:%M").expect("Failed to parse time");
+            data.timezone
+                .from_local_datetime(&naive)
+                .unwrap()
+                .fixed_offset()
+        })
+        .collect();
+
+    let location = Coord {
+        latitude: data.latitude,
+        longitude: data.longitude,
+    };
+
+    let propname = |prop: &str, model: &str| -> String {
+        if models.len() == 1 {
+            prop.to_string()
+        } else {
+            format!("{}_{}", prop, model)
+        }
+    };
+
+    let by_model = models
+        .iter()
+        .map(|model| {
+            let temps = data
+                .hourly
+                .take_field_array::<f64>(&propname("temperature_2m", model));
+            let precips = data
+                .hourly
+                .take_field_array::<f64>(&propname("precipitation", model));
+            let codes = data
+                .hourly
+                .take_field_array::<u8>(&propname("weather_code", model));
+            let apparent_temps = data
+                .hourly
+                .take_field_array::<f64>(&propname("apparent_temperature", model));
+            let humidities = data
+                .hourly
+                .take_field_array::<f64>(&propname("relative_humidity_2m", model));
+            let wind_speeds = data
+                .hourly
+                .take_field_array::<f64>(&propname("wind_speed_10m", model));
+            let wind_dirs = data
+                .hourly
+                .take_field_array::<f64>(&propname("wind_direction_10m", model));
+            let pressures = data
+                .hourly
+                .take_field_array::<f64>(&propname("surface_pressure", model));
+            let precip_probabilities = data
+                .hourly
+                .take_field_array::<f64>(&propname("precipitation_probability", model));
+
+            let forecast: Vec<WeatherPoint> = izip!(
+                temps,
+                precips,
+                codes,
+                apparent_temps,
+                humidities,
+                wind_speeds,
+                wind_dirs,
+                pressures,
+                precip_probabilities
+            )
+            .map(
+                |(
+                    temp,
+                    precip,
+                    code,
+                    apparent_temp,
+                    humidity,
+                    wind_speed,
+                    wind_dir,
+                    pressure,
+                    precipitation_probability,
+                )| WeatherPoint {
+                    temp,
+                    precip,
+                    code: code.map(WmoCode),
+                    apparent_temp,
+                    humidity,
+                    wind_speed,
+                    wind_dir,
+                    pressure,
+                    precipitation_probability,
+                },
+            )
+            .collect();
+
+            (model.to_string(), forecast)
+        })
+        .collect();
+
+    Forecast {
+        times,
+        by_model,
+        timezone: data.timezone,
+        location,
+        units,
+        air_quality: None,
+    }
+}
 
 /// Download weather forecast from Open-Meteo API.
 pub async fn download_forecast(
     latitude: f64,
     longitude: f64,
     models: &[&str],
+    units: Units,
+    forecast_days: u8,
 ) -> anyhow::Result<Forecast> {
-    #[derive(Debug, Deserialize)]
-    struct Response {
+    #[derive(Serialize)]
+    struct Query<'a> {
         latitude: f64,
         longitude: f64,
-        timezone: chrono_tz::Tz,
-        hourly: HourlyData,
+        hourly: &'a str,
+        models: &'a str,
+        forecast_days: u8,
+        timezone: &'a str,
+        temperature_unit: &'a str,
+        precipitation_unit: &'a str,
+        wind_speed_unit: &'a str,
     }
 
-    #[derive(Debug, Deserialize)]
-    struct HourlyData {
-        time: Vec<String>,
-        #[serde(flatten)]
-        data: HashMap<String, Vec<serde_json::Value>>,
-    }
+    let client = reqwest::Client::new();
+    let models_str = models.join(",");
 
-    impl HourlyData {
-        /// Remove `key` from data and deserialize its JSON array into `Vec<Option<T>>`.
-        fn take_field_array<T: DeserializeOwned>(&mut self, key: &str) -> Vec<Option<T>> {
-            self.data
-                .remove(key)
-                .and_then(|v| serde_json::from_value(serde_json::Value::Array(v)).ok())
-                .unwrap_or_default()
-        }
+    let response = client
+        .get("https://api.open-meteo.com/v1/forecast")
+        .query(&Query {
+            latitude,
+            longitude,
+            hourly: HOURLY_FIELDS,
+            models: &models_str,
+            forecast_days,
+            timezone: "auto",
+            temperature_unit: units.temperature_unit(),
+            precipitation_unit: units.precipitation_unit(),
+            wind_speed_unit: units.wind_speed_unit(),
+        })
+        .send()
+        .await
+        .context("HTTP request failed")?;
+
+    if !response.status().is_success() {
+        bail!("API error: {}", response.status());
     }
 
+    let data: ForecastResponse = response.json().await.context("JSON parsing failed")?;
+
+    Ok(forecast_from_response(data, models, units))
+}
+
+/// Download weather forecasts for multiple locations in a single Open-Meteo request.
+///
+/// Open-Meteo accepts comma-separated `latitude`/`longitude` lists and returns either a JSON
+/// array of per-location results, or (for a single coordinate) a bare object; both shapes are
+/// normalized here into one `Forecast` per input coordinate, in the same order as `coords`.
+pub async fn download_forecasts(
+    coords: &[Coord],
+    models: &[&str],
+    units: Units,
+    forecast_days: u8,
+) -> anyhow::Result<Vec<Forecast>> {
     #[derive(Serialize)]
     struct Query<'a> {
-        latitude: f64,
-        longitude: f64,
+        latitude: &'a str,
+        longitude: &'a str,
         hourly: &'a str,
         models: &'a str,
         forecast_days: u8,
         timezone: &'a str,
+        temperature_unit: &'a str,
+        precipitation_unit: &'a str,
+        wind_speed_unit: &'a str,
     }
 
     let client = reqwest::Client::new();
     let models_str = models.join(",");
+    let latitudes = coords
+        .iter()
+        .map(|c| c.latitude.to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+    let longitudes = coords
+        .iter()
+        .map(|c| c.longitude.to_string())
+        .collect::<Vec<_>>()
+        .join(",");
 
     let response = client
         .get("https://api.open-meteo.com/v1/forecast")
+        .query(&Query {
+            latitude: &latitudes,
+            longitude: &longitudes,
+            hourly: HOURLY_FIELDS,
+            models: &models_str,
+            forecast_days,
+            timezone: "auto",
+            temperature_unit: units.temperature_unit(),
+            precipitation_unit: units.precipitation_unit(),
+            wind_speed_unit: units.wind_speed_unit(),
+        })
+        .send()
+        .await
+        .context("HTTP request failed")?;
+
+    if !response.status().is_success() {
+        bail!("API error: {}", response.status());
+    }
+
+    let value: serde_json::Value = response.json().await.context("JSON parsing failed")?;
+    let responses: Vec<ForecastResponse> = match value {
+        serde_json::Value::Array(_) => {
+            serde_json::from_value(value).context("JSON parsing failed")?
+        }
+        single => vec![serde_json::from_value(single).context("JSON parsing failed")?],
+    };
+
+    Ok(responses
+        .into_iter()
+        .map(|data| forecast_from_response(data, models, units))
+        .collect())
+}
+
+/// Open-Meteo `hourly` field list for the historical archive. Lacks `precipitation_probability`,
+/// a forecast-only field the archive doesn't provide.
+const ARCHIVE_HOURLY_FIELDS: &str = "temperature_2m,precipitation,weather_code,apparent_temperature,\
+                              relative_humidity_2m,wind_speed_10m,wind_direction_10m,surface_pressure";
+
+/// Download observed weather for a past date range from Open-Meteo's historical archive, as a
+/// single-model (`"Observed"`) `Forecast` for merging with `download_forecast`'s output via
+/// `crate::data::stitch_historical`.
+pub async fn download_historical(
+    latitude: f64,
+    longitude: f64,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+    units: Units,
+) -> anyhow::Result<Forecast> {
+    #[derive(Debug, Deserialize)]
+    struct Response {
+        latitude: f64,
+        longitude: f64,
+        timezone: chrono_tz::Tz,
+        hourly: HourlyData,
+    }
+
+    #[derive(Serialize)]
+    struct Query<'a> {
+        latitude: f64,
+        longitude: f64,
+        start_date: String,
+        end_date: String,
+        hourly: &'a str,
+        timezone: &'a str,
+        temperature_unit: &'a str,
+        precipitation_unit: &'a str,
+        wind_speed_unit: &'a str,
+    }
+
+    let client = reqwest::Client::new();
+
+    let response = client
+        .get("https://archive-api.open-meteo.com/v1/archive")
         .query(&Query {
             latitude,
             longitude,
-            hourly: "temperature_2m,precipitation,weather_code",
-            models: &models_str,
-            forecast_days: MAX_FORECAST_DAYS,
+            start_date: start_date.format("%Y-%m-%d").to_string(),
+            end_date: end_date.format("%Y-%m-%d").to_string(),
+            hourly: ARCHIVE_HOURLY_FIELDS,
             timezone: "auto",
+            temperature_unit: units.temperature_unit(),
+            precipitation_unit: units.precipitation_unit(),
+            wind_speed_unit: units.wind_speed_unit(),
         })
         .send()
         .await
@@ -90,52 +357,129 @@ pub async fn download_forecast(
         longitude: data.longitude,
     };
 
-    let propname = |prop: &str, model: &str| -> String {
-        if models.len() == 1 {
-            prop.to_string()
-        } else {
-            format!("{}_{}", prop, model)
-        }
-    };
+    let observed: Vec<WeatherPoint> = izip!(
+        data.hourly.take_field_array::<f64>("temperature_2m"),
+        data.hourly.take_field_array::<f64>("precipitation"),
+        data.hourly.take_field_array::<u8>("weather_code"),
+        data.hourly.take_field_array::<f64>("apparent_temperature"),
+        data.hourly.take_field_array::<f64>("relative_humidity_2m"),
+        data.hourly.take_field_array::<f64>("wind_speed_10m"),
+        data.hourly.take_field_array::<f64>("wind_direction_10m"),
+        data.hourly.take_field_array::<f64>("surface_pressure"),
+    )
+    .map(
+        |(temp, precip, code, apparent_temp, humidity, wind_speed, wind_dir, pressure)| {
+            WeatherPoint {
+                temp,
+                precip,
+                code: code.map(WmoCode),
+                apparent_temp,
+                humidity,
+                wind_speed,
+                wind_dir,
+                pressure,
+                precipitation_probability: None,
+            }
+        },
+    )
+    .collect();
 
-    let by_model = models
-        .iter()
-        .map(|model| {
-            let temps = data
-                .hourly
-                .take_field_array::<f64>(&propname("temperature_2m", model));
-            let precips = data
-                .hourly
-                .take_field_array::<f64>(&propname("precipitation", model));
-            let codes = data
-                .hourly
-                .take_field_array::<u8>(&propname("weather_code", model));
+    Ok(Forecast {
+        times,
+        by_model: vec![("Observed".to_string(), observed)],
+        timezone: data.timezone,
+        location,
+        units,
+        air_quality: None,
+    })
+}
 
-            let forecast: Vec<WeatherPoint> = temps
-                .into_iter()
-                .zip(precips)
-                .zip(codes)
-                .map(|((temp, precip), code)| WeatherPoint {
-                    temp,
-                    precip,
-                    code: code.map(WmoCode),
-                })
-                .collect();
+/// Download hourly air-quality data (PM2.5, European AQI, UV index) from Open-Meteo's
+/// air-quality API, aligned to the same hourly time grid `download_forecast` would return.
+pub async fn download_air_quality(
+    latitude: f64,
+    longitude: f64,
+    forecast_days: u8,
+) -> anyhow::Result<(Vec<DateTime<FixedOffset>>, Vec<AirQualityPoint>)> {
+    #[derive(Debug, Deserialize)]
+    struct Response {
+        timezone: chrono_tz::Tz,
+        hourly: Hourly,
+    }
 
-            (model.to_string(), forecast)
+    #[derive(Debug, Deserialize)]
+    struct Hourly {
+        time: Vec<String>,
+        pm2_5: Vec<Option<f64>>,
+        european_aqi: Vec<Option<u32>>,
+        uv_index: Vec<Option<f64>>,
+    }
+
+    #[derive(Serialize)]
+    struct Query<'a> {
+        latitude: f64,
+        longitude: f64,
+        hourly: &'a str,
+        forecast_days: u8,
+        timezone: &'a str,
+    }
+
+    let client = reqwest::Client::new();
+
+    let response = client
+        .get("https://air-quality-api.open-meteo.com/v1/air-quality")
+        .query(&Query {
+            latitude,
+            longitude,
+            hourly: "pm2_5,european_aqi,uv_index",
+            forecast_days,
+            timezone: "auto",
+        })
+        .send()
+        .await
+        .context("HTTP request failed")?;
+
+    if !response.status().is_success() {
+        bail!("API error: {}", response.status());
+    }
+
+    let data: Response = response.json().await.context("JSON parsing failed")?;
+
+    let times = data
+        .hourly
+        .time
+        .iter()
+        .map(|t| {
+            let naive =
+                NaiveDateTime::parse_from_str(t, "%Y-%m-%dT%H:%M").expect("Failed to parse time");
+            data.timezone
+                .from_local_datetime(&naive)
+                .unwrap()
+                .fixed_offset()
         })
         .collect();
 
-    Ok(Forecast {
-        times,
-        by_model,
-        timezone: data.timezone,
-        location,
+    let points = izip!(
+        data.hourly.pm2_5,
+        data.hourly.european_aqi,
+        data.hourly.uv_index
+    )
+    .map(|(pm2_5, aqi, uv_index)| AirQualityPoint {
+        pm2_5,
+        aqi,
+        uv_index,
     })
+    .collect();
+
+    Ok((times, points))
 }
 
 /// Download current weather from Open-Meteo API.
-pub async fn download_current(latitude: f64, longitude: f64) -> anyhow::Result<Current> {
+pub async fn download_current(
+    latitude: f64,
+    longitude: f64,
+    units: Units,
+) -> anyhow::Result<Current> {
     #[derive(Debug, Deserialize)]
     struct Response {
         latitude: f64,
@@ -150,6 +494,11 @@ pub async fn download_current(latitude: f64, longitude: f64) -> anyhow::Result<C
         temperature_2m: Option<f64>,
         precipitation: Option<f64>,
         weather_code: Option<u8>,
+        apparent_temperature: Option<f64>,
+        relative_humidity_2m: Option<f64>,
+        wind_speed_10m: Option<f64>,
+        wind_direction_10m: Option<f64>,
+        surface_pressure: Option<f64>,
     }
 
     #[derive(Serialize)]
@@ -158,6 +507,9 @@ pub async fn download_current(latitude: f64, longitude: f64) -> anyhow::Result<C
         longitude: f64,
         current: &'a str,
         timezone: &'a str,
+        temperature_unit: &'a str,
+        precipitation_unit: &'a str,
+        wind_speed_unit: &'a str,
     }
 
     let client = reqwest::Client::new();
@@ -167,8 +519,12 @@ pub async fn download_current(latitude: f64, longitude: f64) -> anyhow::Result<C
         .query(&Query {
             latitude,
             longitude,
-            current: "temperature_2m,precipitation,weather_code",
+            current: "temperature_2m,precipitation,weather_code,apparent_temperature,\
+                      relative_humidity_2m,wind_speed_10m,wind_direction_10m,surface_pressure",
             timezone: "auto",
+            temperature_unit: units.temperature_unit(),
+            precipitation_unit: units.precipitation_unit(),
+            wind_speed_unit: units.wind_speed_unit(),
         })
         .send()
         .await
@@ -197,11 +553,45 @@ pub async fn download_current(latitude: f64, longitude: f64) -> anyhow::Result<C
         temp: data.current.temperature_2m,
         precip: data.current.precipitation,
         code: data.current.weather_code.map(WmoCode),
+        apparent_temp: data.current.apparent_temperature,
+        humidity: data.current.relative_humidity_2m,
+        wind_speed: data.current.wind_speed_10m,
+        wind_dir: data.current.wind_direction_10m,
+        pressure: data.current.surface_pressure,
+        precipitation_probability: None,
     };
 
     Ok(Current {
         weather,
         time,
         location,
+        units,
     })
 }
+
+/// Spawn a background task that polls `download_current` on a fixed interval and pushes each
+/// result (success or error) over a channel.
+///
+/// Lets a long-running program (e.g. a status bar or dashboard) receive refreshed weather
+/// without manually re-calling `download_current`. The polling task exits once the returned
+/// receiver is dropped.
+pub fn watch_current(
+    latitude: f64,
+    longitude: f64,
+    units: Units,
+    interval: Duration,
+) -> mpsc::Receiver<anyhow::Result<Current>> {
+    let (tx, rx) = mpsc::channel(1);
+
+    tokio::spawn(async move {
+        loop {
+            let result = download_current(latitude, longitude, units).await;
+            if tx.send(result).await.is_err() {
+                break;
+            }
+            tokio::time::sleep(interval).await;
+        }
+    });
+
+    rx
+}
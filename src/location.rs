@@ -73,10 +73,16 @@ fn parse_coordinates(s: &str) -> Option<Location> {
 
 /// Resolve a location string to geographic coordinates.
 ///
-/// Accepts either a coordinate pair (e.g., "45.8150,15.9819") or a place name
-/// (e.g., "London"). Coordinates are validated to be within valid ranges.
-/// Place names are resolved using the Nominatim geocoding API.
+/// Accepts a coordinate pair (e.g., "45.8150,15.9819"), a place name (e.g.,
+/// "London"), or the literal token "auto" (or an empty string) to geolocate
+/// the machine via its public IP instead. Coordinates are validated to be
+/// within valid ranges. Place names are resolved using the Nominatim
+/// geocoding API.
 pub fn resolve_location(s: &str) -> anyhow::Result<Location> {
+    if s.is_empty() || s.eq_ignore_ascii_case("auto") {
+        return resolve_location_by_ip();
+    }
+
     if let Some(location) = parse_coordinates(s) {
         return Ok(location);
     }
@@ -108,6 +114,68 @@ pub fn resolve_location(s: &str) -> anyhow::Result<Location> {
     })
 }
 
+#[derive(Debug, Deserialize)]
+struct IpLocationResponse {
+    latitude: f64,
+    longitude: f64,
+    city: String,
+    country_name: String,
+}
+
+impl From<IpLocationResponse> for Location {
+    fn from(data: IpLocationResponse) -> Self {
+        Location {
+            display_name: format!("{}, {} (autolocated)", data.city, data.country_name),
+            latitude: data.latitude,
+            longitude: data.longitude,
+        }
+    }
+}
+
+/// Geolocate the machine via its public IP, for the "auto"/empty-string fallback in
+/// `resolve_location`.
+fn resolve_location_by_ip() -> anyhow::Result<Location> {
+    let client = reqwest::blocking::Client::new();
+    let response = client
+        .get("https://ipapi.co/json/")
+        .send()
+        .context("IP geolocation request failed")?;
+
+    if !response.status().is_success() {
+        bail!("IP geolocation API error: {}", response.status());
+    }
+
+    let data: IpLocationResponse = response
+        .json()
+        .context("IP geolocation JSON parsing failed")?;
+
+    Ok(data.into())
+}
+
+/// Resolve the caller's approximate location via a keyless IP-geolocation lookup.
+///
+/// Used as a fallback when the user omits a location argument, so the CLI can be run with
+/// zero arguments and still produce a forecast near wherever it's running.
+pub async fn resolve_current_location() -> anyhow::Result<Location> {
+    let client = reqwest::Client::new();
+    let response = client
+        .get("https://ipapi.co/json/")
+        .send()
+        .await
+        .context("IP geolocation request failed")?;
+
+    if !response.status().is_success() {
+        bail!("IP geolocation API error: {}", response.status());
+    }
+
+    let data: IpLocationResponse = response
+        .json()
+        .await
+        .context("IP geolocation JSON parsing failed")?;
+
+    Ok(data.into())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -166,4 +234,11 @@ mod tests {
         assert!(parse_coordinates("45").is_none());
         assert!(parse_coordinates("45,15,20").is_none());
     }
+
+    #[test]
+    fn empty_string_is_not_a_coordinate_pair() {
+        // Guards against "auto"-fallback regressions: resolve_location relies on
+        // parse_coordinates rejecting "" so it can route to IP geolocation instead.
+        assert!(parse_coordinates("").is_none());
+    }
 }
@@ -1,15 +1,18 @@
 mod location;
 mod openmeteo_fetch;
 mod table;
+mod time;
 
-use chrono::{DateTime, FixedOffset, Local, Timelike};
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, FixedOffset, Local, Timelike};
 use clap::{Parser, Subcommand};
 
 use itertools::Itertools;
 use location::resolve_location;
 use openmeteo_fetch::{Current, Forecast, WeatherPoint};
 use table::Table;
-use time::{parse_date_range, resolve_time_range};
+use time::{parse_date_range, resolve_time_range, resolve_timezone};
 use unicode_width::UnicodeWidthStr;
 
 #[derive(Parser)]
@@ -28,7 +31,9 @@ enum Command {
         /// Location name or lat,long pair
         location: String,
 
-        /// YYYY-MM-DD, 'today', 'tomorrow', or weekday, or date1..date2
+        /// YYYY-MM-DD, 'today', 'tomorrow', or weekday, or date1..date2.
+        /// Add a clock-time sub-range with '@HH:MM' or a trailing hour, e.g.
+        /// "today 6..18" or "tomorrow@09:00..tomorrow@21:00".
         #[arg(default_value = "today")]
         dates: String,
 
@@ -47,6 +52,12 @@ enum Command {
         /// Verbose output
         #[arg(short, long)]
         verbose: bool,
+
+        /// IANA timezone to interpret dates in (e.g. "America/New_York").
+        /// Defaults to the host's local timezone, falling back to UTC if it
+        /// can't be detected.
+        #[arg(long)]
+        timezone: Option<String>,
     },
     /// Fetch current weather for a given location
     Current {
@@ -190,41 +201,119 @@ fn build_forecast_table(
     table
 }
 
+/// Merge observed ("Observed") and forecast data into one continuous series keyed on the union of
+/// both time axes, so `build_forecast_table` can render a seamless past-through-future view.
+///
+/// Either side may be absent: a wholly-past range has no `forecast`, a wholly-future range has no
+/// `historical`. Where the two overlap or one has no data for a given time, missing points are
+/// filled in as `None` rather than dropped, so every model's column lines up with `times`.
+fn stitch_historical(
+    historical: Option<Forecast>,
+    forecast: Option<Forecast>,
+) -> (Vec<DateTime<FixedOffset>>, Vec<(String, Vec<WeatherPoint>)>) {
+    let (historical, forecast) = match (historical, forecast) {
+        (Some(h), Some(f)) => (h, f),
+        (Some(h), None) => return (h.times, h.by_model),
+        (None, Some(f)) => return (f.times, f.by_model),
+        (None, None) => return (Vec::new(), Vec::new()),
+    };
+
+    let mut times: Vec<DateTime<FixedOffset>> = historical
+        .times
+        .iter()
+        .chain(&forecast.times)
+        .copied()
+        .collect();
+    times.sort();
+    times.dedup();
+
+    let align =
+        |src_times: &[DateTime<FixedOffset>], points: &[WeatherPoint]| -> Vec<WeatherPoint> {
+            let by_time: HashMap<DateTime<FixedOffset>, &WeatherPoint> =
+                src_times.iter().copied().zip(points).collect();
+            times
+                .iter()
+                .map(|t| {
+                    by_time.get(t).copied().cloned().unwrap_or(WeatherPoint {
+                        temp: None,
+                        precip: None,
+                        code: None,
+                    })
+                })
+                .collect()
+        };
+
+    let mut by_model = vec![(
+        historical.by_model[0].0.clone(),
+        align(&historical.times, &historical.by_model[0].1),
+    )];
+    for (model, points) in &forecast.by_model {
+        by_model.push((model.clone(), align(&forecast.times, points)));
+    }
+
+    (times, by_model)
+}
+
 /// Handle the `forecast` subcommand: fetch and display weather forecast.
 ///
 /// Resolves the location (by name or coordinates), parses the date range, downloads forecast data
-/// from Open-Meteo for the requested models, and prints the result as a formatted table.
-async fn do_forecast(
+/// from Open-Meteo for the requested models, and prints the result as a formatted table. When the
+/// range starts before today, the past portion is served from the historical archive instead of
+/// the forecast models, and stitched together with any future portion into one table.
+fn do_forecast(
     location: &str,
     dates: &str,
     models: &[String],
     full: bool,
     verbose: bool,
+    timezone: Option<&str>,
 ) -> anyhow::Result<()> {
-    let location = resolve_location(location).await?;
+    let location = resolve_location(location)?;
     let date_range = parse_date_range(dates)?;
+    let timezone = resolve_timezone(timezone)?;
 
     println!("Forecast for {}", location.display_name);
 
-    let models: Vec<&str> = models.iter().map(|s| s.as_str()).collect();
-    let mut forecast = Forecast::download(location.latitude, location.longitude, &models).await?;
+    let now = Local::now().with_timezone(&timezone).fixed_offset();
+    let time_range = resolve_time_range(date_range, timezone, now);
+    let today = now.date_naive();
+
+    let historical = if time_range.0.date_naive() < today {
+        let last_included_date = (time_range.1 - Duration::seconds(1)).date_naive();
+        let end_date = std::cmp::min(last_included_date, today - Duration::days(1));
+        Some(Forecast::download_historical(
+            location.latitude,
+            location.longitude,
+            time_range.0.date_naive(),
+            end_date,
+        )?)
+    } else {
+        None
+    };
 
-    let now = Local::now()
-        .with_timezone(&forecast.timezone)
-        .fixed_offset();
-    if !full {
-        forecast.compress(now.date_naive());
-    }
-
-    let time_range = resolve_time_range(date_range, forecast.timezone, now);
+    let models: Vec<&str> = models.iter().map(|s| s.as_str()).collect();
+    let forecast = if time_range.1 > now {
+        let mut forecast =
+            Forecast::download(location.latitude, location.longitude, &models)?;
+        if !full {
+            forecast.compress(today);
+        }
+        Some(forecast)
+    } else {
+        None
+    };
 
     if verbose {
-        println!("Grid-cell location: {}", forecast.location.link());
-        println!("Timezone: {}", forecast.timezone);
+        if let Some(reference) = forecast.as_ref().or(historical.as_ref()) {
+            println!("Grid-cell location: {}", reference.location.link());
+            println!("Grid-cell timezone: {}", reference.timezone);
+        }
+        println!("Display timezone: {timezone}");
         println!("Interval: [{}, {})", time_range.0, time_range.1);
     }
 
-    build_forecast_table(&forecast.times, &forecast.by_model, time_range).print();
+    let (times, by_model) = stitch_historical(historical, forecast);
+    build_forecast_table(&times, &by_model, time_range).print();
     Ok(())
 }
 
@@ -232,12 +321,12 @@ async fn do_forecast(
 ///
 /// Resolves the location (by name or coordinates), downloads current weather from Open-Meteo,
 /// and prints the result as a single-row table.
-async fn do_current(location: &str, verbose: bool) -> anyhow::Result<()> {
-    let location = resolve_location(location).await?;
+fn do_current(location: &str, verbose: bool) -> anyhow::Result<()> {
+    let location = resolve_location(location)?;
 
     println!("Current weather for {}", location.display_name);
 
-    let current = Current::download(location.latitude, location.longitude).await?;
+    let current = Current::download(location.latitude, location.longitude)?;
 
     if verbose {
         println!("Grid-cell location: {}", current.location.link());
@@ -258,8 +347,7 @@ async fn do_current(location: &str, verbose: bool) -> anyhow::Result<()> {
     Ok(())
 }
 
-#[tokio::main]
-async fn main() -> anyhow::Result<()> {
+fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
 
     match cli.command {
@@ -269,419 +357,15 @@ async fn main() -> anyhow::Result<()> {
             models,
             full,
             verbose,
-        } => do_forecast(&location, &dates, &models, full, verbose).await,
-        Command::Current { location, verbose } => do_current(&location, verbose).await,
-    }
-}
-
-mod time {
-    use chrono::{DateTime, Datelike, Duration, FixedOffset, NaiveDate, NaiveTime, Weekday};
-    use chrono_tz::Tz;
-
-    #[derive(Debug, Copy, Clone, PartialEq)]
-    pub enum RequestedDate {
-        Today,
-        Tomorrow,
-        RelativeDays(u8),
-        Weekday(Weekday),
-        Absolute(NaiveDate),
-    }
-
-    fn parse_weekday(s: &str) -> Option<Weekday> {
-        match s {
-            "mon" | "monday" => Some(Weekday::Mon),
-            "tue" | "tuesday" => Some(Weekday::Tue),
-            "wed" | "wednesday" => Some(Weekday::Wed),
-            "thu" | "thursday" => Some(Weekday::Thu),
-            "fri" | "friday" => Some(Weekday::Fri),
-            "sat" | "saturday" => Some(Weekday::Sat),
-            "sun" | "sunday" => Some(Weekday::Sun),
-            _ => None,
-        }
-    }
-
-    fn parse_date(s: &str) -> anyhow::Result<RequestedDate> {
-        use anyhow::Context;
-        let s = s.to_lowercase();
-        match s.as_str() {
-            "today" => Ok(RequestedDate::Today),
-            "tomorrow" => Ok(RequestedDate::Tomorrow),
-            _ => {
-                if let Some(weekday) = parse_weekday(&s) {
-                    Ok(RequestedDate::Weekday(weekday))
-                } else if let Some(days) = s.strip_prefix('+').and_then(|n| n.parse::<u8>().ok()) {
-                    Ok(RequestedDate::RelativeDays(days))
-                } else {
-                    NaiveDate::parse_from_str(&s, "%Y-%m-%d")
-                        .map(RequestedDate::Absolute)
-                        .context(
-                            "dates must be YYYY-MM-DD, +N, weekday name, 'today' or 'tomorrow'",
-                        )
-                }
-            }
-        }
-    }
-
-    pub fn parse_date_range(s: &str) -> anyhow::Result<(RequestedDate, RequestedDate)> {
-        if let Some(pos) = s.find("..") {
-            let a = parse_date(&s[..pos])?;
-            let b = parse_date(&s[pos + 2..])?;
-            Ok((a, b))
-        } else {
-            let d = parse_date(s)?;
-            Ok((d, d))
-        }
-    }
-
-    fn resolve_date(
-        dt: RequestedDate,
-        relative_to: NaiveDate,
-        weekday_start_at: NaiveDate,
-    ) -> NaiveDate {
-        match dt {
-            RequestedDate::Today => relative_to,
-            RequestedDate::Tomorrow => relative_to + Duration::days(1),
-            RequestedDate::RelativeDays(n) => relative_to + Duration::days(n.into()),
-            RequestedDate::Weekday(wanted) => {
-                let mut date = weekday_start_at;
-                while date.weekday() != wanted {
-                    date += Duration::days(1);
-                }
-                date
-            }
-            RequestedDate::Absolute(d) => d,
-        }
-    }
-
-    /// Convert an inclusive date range to a half-open time interval.
-    ///
-    /// Input dates are inclusive (e.g., "mon..wed" means Monday through Wednesday).
-    /// Output is a half-open interval `[start, end)` suitable for filtering hourly data.
-    /// The start time is clamped to `relative_to` to avoid showing past hours.
-    pub fn resolve_time_range(
-        (mut start_date, mut end_date): (RequestedDate, RequestedDate),
-        timezone: Tz,
-        relative_to: DateTime<FixedOffset>,
-    ) -> (DateTime<FixedOffset>, DateTime<FixedOffset>) {
-        use chrono::TimeZone;
-
-        let original_date = relative_to.date_naive();
-
-        // Open-Meteo provides forecasts at hour starts, so after 23:00 there's no more data
-        // for "today". Since start is clamped to `relative_to`, shift to "tomorrow" to avoid
-        // an empty forecast. We use 22:55 as the cutoff to account for network latency.
-        const CUTOFF_TIME: NaiveTime = NaiveTime::from_hms_opt(22, 55, 0).unwrap();
-
-        if relative_to.time() > CUTOFF_TIME {
-            if start_date == RequestedDate::Today {
-                start_date = RequestedDate::Tomorrow;
-            }
-            if end_date == RequestedDate::Today {
-                end_date = RequestedDate::Tomorrow;
-            }
-        }
-
-        // We've updated start and end date, but still pass the original relative_to to
-        // resolve_date(), so that "+2" or "thursday" refer to the correct date.
-        let start_resolved = resolve_date(start_date, original_date, original_date);
-        let end_resolved = resolve_date(end_date, original_date, start_resolved);
-
-        let start_time = timezone
-            .from_local_datetime(&start_resolved.and_time(NaiveTime::MIN))
-            .unwrap()
-            .fixed_offset();
-        let start_time = std::cmp::max(start_time, relative_to);
-
-        let end_resolved = end_resolved + Duration::days(1);
-        let end_time = timezone
-            .from_local_datetime(&end_resolved.and_time(NaiveTime::MIN))
-            .unwrap()
-            .fixed_offset();
-
-        (start_time, end_time)
-    }
-
-    #[cfg(test)]
-    mod tests {
-        use super::*;
-        use chrono::{TimeZone, Timelike};
-
-        fn make_time(hour: u32, minute: u32) -> DateTime<FixedOffset> {
-            // Use a Wednesday (2025-01-15) as the reference date for weekday tests
-            FixedOffset::east_opt(0)
-                .unwrap()
-                .with_ymd_and_hms(2025, 1, 15, hour, minute, 0)
-                .unwrap()
-        }
-
-        // --- parse_date tests ---
-
-        #[test]
-        fn parse_date_today_tomorrow() {
-            assert_eq!(parse_date("today").unwrap(), RequestedDate::Today);
-            assert_eq!(parse_date("tomorrow").unwrap(), RequestedDate::Tomorrow);
-        }
-
-        #[test]
-        fn parse_date_case_insensitive() {
-            assert_eq!(parse_date("TODAY").unwrap(), RequestedDate::Today);
-            assert_eq!(parse_date("Tomorrow").unwrap(), RequestedDate::Tomorrow);
-            assert_eq!(
-                parse_date("MONDAY").unwrap(),
-                RequestedDate::Weekday(Weekday::Mon)
-            );
-        }
-
-        #[test]
-        fn parse_date_weekdays() {
-            assert_eq!(
-                parse_date("mon").unwrap(),
-                RequestedDate::Weekday(Weekday::Mon)
-            );
-            assert_eq!(
-                parse_date("monday").unwrap(),
-                RequestedDate::Weekday(Weekday::Mon)
-            );
-            assert_eq!(
-                parse_date("tue").unwrap(),
-                RequestedDate::Weekday(Weekday::Tue)
-            );
-            assert_eq!(
-                parse_date("wed").unwrap(),
-                RequestedDate::Weekday(Weekday::Wed)
-            );
-            assert_eq!(
-                parse_date("thu").unwrap(),
-                RequestedDate::Weekday(Weekday::Thu)
-            );
-            assert_eq!(
-                parse_date("fri").unwrap(),
-                RequestedDate::Weekday(Weekday::Fri)
-            );
-            assert_eq!(
-                parse_date("sat").unwrap(),
-                RequestedDate::Weekday(Weekday::Sat)
-            );
-            assert_eq!(
-                parse_date("sun").unwrap(),
-                RequestedDate::Weekday(Weekday::Sun)
-            );
-            assert_eq!(
-                parse_date("sunday").unwrap(),
-                RequestedDate::Weekday(Weekday::Sun)
-            );
-        }
-
-        #[test]
-        fn parse_date_relative_days() {
-            assert_eq!(parse_date("+0").unwrap(), RequestedDate::RelativeDays(0));
-            assert_eq!(parse_date("+1").unwrap(), RequestedDate::RelativeDays(1));
-            assert_eq!(parse_date("+7").unwrap(), RequestedDate::RelativeDays(7));
-            assert_eq!(parse_date("+16").unwrap(), RequestedDate::RelativeDays(16));
-        }
-
-        #[test]
-        fn parse_date_absolute() {
-            assert_eq!(
-                parse_date("2025-01-15").unwrap(),
-                RequestedDate::Absolute(NaiveDate::from_ymd_opt(2025, 1, 15).unwrap())
-            );
-            assert_eq!(
-                parse_date("2024-12-31").unwrap(),
-                RequestedDate::Absolute(NaiveDate::from_ymd_opt(2024, 12, 31).unwrap())
-            );
-        }
-
-        #[test]
-        fn parse_date_invalid() {
-            assert!(parse_date("").is_err());
-            assert!(parse_date("yesterday").is_err());
-            assert!(parse_date("15-01-2025").is_err()); // wrong order
-            assert!(parse_date("2025/01/15").is_err()); // wrong separator
-            assert!(parse_date("invalid").is_err());
-        }
-
-        // --- parse_date_range tests ---
-
-        #[test]
-        fn parse_date_range_single() {
-            let (start, end) = parse_date_range("today").unwrap();
-            assert_eq!(start, RequestedDate::Today);
-            assert_eq!(end, RequestedDate::Today);
-        }
-
-        #[test]
-        fn parse_date_range_range() {
-            let (start, end) = parse_date_range("today..tomorrow").unwrap();
-            assert_eq!(start, RequestedDate::Today);
-            assert_eq!(end, RequestedDate::Tomorrow);
-
-            let (start, end) = parse_date_range("mon..fri").unwrap();
-            assert_eq!(start, RequestedDate::Weekday(Weekday::Mon));
-            assert_eq!(end, RequestedDate::Weekday(Weekday::Fri));
-
-            let (start, end) = parse_date_range("+1..+3").unwrap();
-            assert_eq!(start, RequestedDate::RelativeDays(1));
-            assert_eq!(end, RequestedDate::RelativeDays(3));
-        }
-
-        #[test]
-        fn parse_date_range_invalid() {
-            assert!(parse_date_range("invalid..today").is_err());
-            assert!(parse_date_range("today..invalid").is_err());
-            assert!(parse_date_range("..today").is_err());
-            assert!(parse_date_range("today..").is_err());
-        }
-
-        // --- resolve_time_range tests ---
-
-        /// Test helper that parses a date range string and resolves it in UTC.
-        fn test_resolve(
-            dates: &str,
-            relative_to: DateTime<FixedOffset>,
-        ) -> (DateTime<FixedOffset>, DateTime<FixedOffset>) {
-            let date_range = parse_date_range(dates).unwrap();
-            resolve_time_range(date_range, chrono_tz::UTC, relative_to)
-        }
-
-        #[test]
-        fn resolve_time_range_today_before_cutoff() {
-            let relative_to = make_time(12, 0); // noon
-            let (start, end) = test_resolve("today", relative_to);
-            // Start should be clamped to relative_to (noon)
-            assert_eq!(start.hour(), 12);
-            // End should be midnight of the next day
-            assert_eq!(
-                end.date_naive(),
-                NaiveDate::from_ymd_opt(2025, 1, 16).unwrap()
-            );
-            assert_eq!(end.hour(), 0);
-        }
-
-        #[test]
-        fn resolve_time_range_today_after_cutoff() {
-            let relative_to = make_time(23, 0); // after 22:55
-            let (start, end) = test_resolve("today", relative_to);
-            // "today" should shift to tomorrow due to cutoff
-            assert_eq!(
-                start.date_naive(),
-                NaiveDate::from_ymd_opt(2025, 1, 16).unwrap()
-            );
-            assert_eq!(start.hour(), 0);
-            // End should be midnight of the day after tomorrow
-            assert_eq!(
-                end.date_naive(),
-                NaiveDate::from_ymd_opt(2025, 1, 17).unwrap()
-            );
-        }
-
-        #[test]
-        fn resolve_time_range_at_cutoff_boundary() {
-            // Exactly at 22:55 should NOT trigger the shift (we use >)
-            let (start, _) = test_resolve("today", make_time(22, 55));
-            assert_eq!(
-                start.date_naive(),
-                NaiveDate::from_ymd_opt(2025, 1, 15).unwrap()
-            );
-
-            // One minute later should trigger the shift
-            let (start, _) = test_resolve("today", make_time(22, 56));
-            assert_eq!(
-                start.date_naive(),
-                NaiveDate::from_ymd_opt(2025, 1, 16).unwrap()
-            );
-        }
-
-        #[test]
-        fn resolve_time_range_relative_days() {
-            let (start, end) = test_resolve("+2..+3", make_time(10, 0));
-            // +2 from 2025-01-15 is 2025-01-17
-            assert_eq!(
-                start.date_naive(),
-                NaiveDate::from_ymd_opt(2025, 1, 17).unwrap()
-            );
-            // +3 from 2025-01-15 is 2025-01-18, end is midnight of next day
-            assert_eq!(
-                end.date_naive(),
-                NaiveDate::from_ymd_opt(2025, 1, 19).unwrap()
-            );
-        }
-
-        #[test]
-        fn resolve_time_range_weekday() {
-            // Reference is Wednesday 2025-01-15
-            let (start, end) = test_resolve("fri..sun", make_time(10, 0));
-            // Friday after Wednesday 2025-01-15 is 2025-01-17
-            assert_eq!(
-                start.date_naive(),
-                NaiveDate::from_ymd_opt(2025, 1, 17).unwrap()
-            );
-            // Sunday after Friday is 2025-01-19, end is midnight of next day
-            assert_eq!(
-                end.date_naive(),
-                NaiveDate::from_ymd_opt(2025, 1, 20).unwrap()
-            );
-        }
-
-        #[test]
-        fn resolve_time_range_absolute_ignores_cutoff() {
-            let relative_to = make_time(23, 30); // after cutoff
-            let (start, end) = test_resolve("2025-01-15", relative_to);
-            // Absolute dates should not be affected by the cutoff
-            // But start is still clamped to relative_to
-            assert_eq!(start.hour(), 23);
-            assert_eq!(start.minute(), 30);
-            assert_eq!(
-                end.date_naive(),
-                NaiveDate::from_ymd_opt(2025, 1, 16).unwrap()
-            );
-        }
-
-        #[test]
-        fn resolve_time_range_start_clamped_to_relative_to() {
-            // If relative_to is in the afternoon, start should be clamped
-            let (start, _) = test_resolve("today", make_time(15, 30));
-            assert_eq!(start.hour(), 15);
-            assert_eq!(start.minute(), 30);
-        }
-
-        #[test]
-        fn resolve_time_range_respects_timezone() {
-            // 10:00 UTC on 2025-01-15
-            let relative_to = FixedOffset::east_opt(0)
-                .unwrap()
-                .with_ymd_and_hms(2025, 1, 15, 10, 0, 0)
-                .unwrap();
-
-            // In UTC, "tomorrow" starts at 2025-01-16 00:00:00 UTC
-            let (start_utc, _) = resolve_time_range(
-                parse_date_range("tomorrow").unwrap(),
-                chrono_tz::UTC,
-                relative_to,
-            );
-            assert_eq!(
-                start_utc.date_naive(),
-                NaiveDate::from_ymd_opt(2025, 1, 16).unwrap()
-            );
-            assert_eq!(start_utc.hour(), 0);
-            assert_eq!(start_utc.offset().local_minus_utc(), 0);
-
-            // In Europe/Zagreb (UTC+1 in winter), "tomorrow" starts at 2025-01-16 00:00:00
-            // local, which is 2025-01-15 23:00:00 UTC
-            let (start_zagreb, _) = resolve_time_range(
-                parse_date_range("tomorrow").unwrap(),
-                chrono_tz::Europe::Zagreb,
-                relative_to,
-            );
-            assert_eq!(
-                start_zagreb.date_naive(),
-                NaiveDate::from_ymd_opt(2025, 1, 16).unwrap()
-            );
-            assert_eq!(start_zagreb.hour(), 0);
-            assert_eq!(start_zagreb.offset().local_minus_utc(), 3600); // UTC+1
-
-            // The Zagreb time should be 1 hour earlier in absolute terms
-            assert_eq!(start_zagreb.timestamp(), start_utc.timestamp() - 3600);
-        }
+            timezone,
+        } => do_forecast(
+            &location,
+            &dates,
+            &models,
+            full,
+            verbose,
+            timezone.as_deref(),
+        ),
+        Command::Current { location, verbose } => do_current(&location, verbose),
     }
 }
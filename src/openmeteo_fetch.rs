@@ -171,6 +171,100 @@ impl Forecast {
         })
     }
 
+    /// Download observed weather for a past date range from Open-Meteo's archive API.
+    ///
+    /// Unlike `download`, the archive has no model dimension: it reports a single set of
+    /// reanalysis-derived observations, which we expose as one `"Observed"` series so it slots
+    /// into the same `by_model` shape as forecast data.
+    pub fn download_historical(
+        latitude: f64,
+        longitude: f64,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> anyhow::Result<Self> {
+        #[derive(Debug, Deserialize)]
+        struct Response {
+            latitude: f64,
+            longitude: f64,
+            timezone: Tz,
+            hourly: HourlyData,
+        }
+
+        #[derive(Debug, Deserialize)]
+        struct HourlyData {
+            time: Vec<String>,
+            temperature_2m: Vec<Option<f64>>,
+            precipitation: Vec<Option<f64>>,
+            weather_code: Vec<Option<i32>>,
+        }
+
+        #[derive(Serialize)]
+        struct Query<'a> {
+            latitude: f64,
+            longitude: f64,
+            start_date: String,
+            end_date: String,
+            hourly: &'a str,
+            timezone: &'a str,
+        }
+
+        let client = reqwest::blocking::Client::new();
+
+        let response = client
+            .get("https://archive-api.open-meteo.com/v1/archive")
+            .query(&Query {
+                latitude,
+                longitude,
+                start_date: start_date.format("%Y-%m-%d").to_string(),
+                end_date: end_date.format("%Y-%m-%d").to_string(),
+                hourly: "temperature_2m,precipitation,weather_code",
+                timezone: "auto",
+            })
+            .send()
+            .context("HTTP request failed")?;
+
+        if !response.status().is_success() {
+            bail!("API error: {}", response.status());
+        }
+
+        let data: Response = response.json().context("JSON parsing failed")?;
+
+        let times: Vec<DateTime<FixedOffset>> = data
+            .hourly
+            .time
+            .iter()
+            .map(|t| {
+                let naive = NaiveDateTime::parse_from_str(t, "%Y-%m-%dT%H:%M")
+                    .expect("Failed to parse time");
+                data.timezone
+                    .from_local_datetime(&naive)
+                    .unwrap()
+                    .fixed_offset()
+            })
+            .collect();
+
+        let location = Coord {
+            latitude: data.latitude,
+            longitude: data.longitude,
+        };
+
+        let observed: Vec<WeatherPoint> = data
+            .hourly
+            .temperature_2m
+            .into_iter()
+            .zip(data.hourly.precipitation)
+            .zip(data.hourly.weather_code)
+            .map(|((temp, precip), code)| WeatherPoint { temp, precip, code })
+            .collect();
+
+        Ok(Forecast {
+            times,
+            by_model: vec![("Observed".to_string(), observed)],
+            timezone: data.timezone,
+            location,
+        })
+    }
+
     /// Compress forecast data: keep hourly for today, use 3-hour intervals for other days.
     ///
     /// For compressed intervals, temperature is averaged, precipitation is summed, and the most
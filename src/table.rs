@@ -1,20 +1,349 @@
+use std::io::IsTerminal;
+
+use clap::ValueEnum;
+use terminal_size::{terminal_size, Width};
 use unicode_width::UnicodeWidthStr;
 
+/// Compute the visible display width of a string, skipping ANSI CSI escape sequences.
+///
+/// A CSI sequence is `ESC [` followed by parameter/intermediate bytes in `0x20..=0x3F`
+/// and terminated by a final byte in `0x40..=0x7E` (e.g. `\x1b[31m`). Such sequences take
+/// up zero display columns, so callers that colorize headers or cells still align with
+/// plain output.
+fn display_width(s: &str) -> usize {
+    let bytes = s.as_bytes();
+    let mut width = 0;
+    let mut visible_start = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == 0x1b && bytes.get(i + 1) == Some(&b'[') {
+            width += s[visible_start..i].width();
+            let mut j = i + 2;
+            while j < bytes.len() && (0x20..=0x3f).contains(&bytes[j]) {
+                j += 1;
+            }
+            if j < bytes.len() && (0x40..=0x7e).contains(&bytes[j]) {
+                j += 1;
+            }
+            i = j;
+            visible_start = i;
+        } else {
+            i += 1;
+        }
+    }
+    width += s[visible_start..].width();
+    width
+}
+
 struct Column {
     header: String,
     data: Vec<String>,
 }
 
+/// Detect the terminal width in columns, falling back to 80 when stdout isn't a tty or the
+/// width can't be determined.
+pub fn detect_terminal_width() -> usize {
+    if !std::io::stdout().is_terminal() {
+        return 80;
+    }
+    terminal_size()
+        .map(|(Width(w), _)| w as usize)
+        .unwrap_or(80)
+}
+
+/// Greedily word-wrap `s` to `width` display columns, returning the wrapped text with lines
+/// joined by `\n`. A single word wider than `width` is left on its own (overflowing) line.
+fn word_wrap(s: &str, width: usize) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    let mut current = String::new();
+    let mut current_width = 0;
+
+    for word in s.split_whitespace() {
+        let word_width = display_width(word);
+        let extra = if current.is_empty() { 0 } else { 1 };
+        if !current.is_empty() && current_width + extra + word_width > width {
+            lines.push(std::mem::take(&mut current));
+            current_width = 0;
+        }
+        if !current.is_empty() {
+            current.push(' ');
+            current_width += 1;
+        }
+        current.push_str(word);
+        current_width += word_width;
+    }
+    if !current.is_empty() || lines.is_empty() {
+        lines.push(current);
+    }
+    lines.join("\n")
+}
+
 // A closed group: columns from some range have been finalized under this name.
 struct Group {
     name: Option<String>,
     count: usize,
 }
 
+/// Border/layout style for `Table::print`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum TableStyle {
+    /// Space-separated columns with trailing whitespace trimmed (the original, default look).
+    #[default]
+    Bare,
+    /// ASCII box-drawing (`+`, `-`, `|`).
+    Ascii,
+    /// Unicode box-drawing (`┌─┬┐│├┼┤└┴┘`).
+    Unicode,
+}
+
+impl std::fmt::Display for TableStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.to_possible_value()
+            .expect("no values are skipped")
+            .get_name()
+            .fmt(f)
+    }
+}
+
+struct BorderChars {
+    horizontal: char,
+    vertical: char,
+    cross: char,
+    tee_down: char,
+    top_left: char,
+    top_mid: char,
+    top_right: char,
+    bottom_left: char,
+    bottom_mid: char,
+    bottom_right: char,
+    left_mid: char,
+    right_mid: char,
+}
+
+impl TableStyle {
+    fn chars(self) -> Option<BorderChars> {
+        match self {
+            TableStyle::Bare => None,
+            TableStyle::Ascii => Some(BorderChars {
+                horizontal: '-',
+                vertical: '|',
+                cross: '+',
+                tee_down: '+',
+                top_left: '+',
+                top_mid: '+',
+                top_right: '+',
+                bottom_left: '+',
+                bottom_mid: '+',
+                bottom_right: '+',
+                left_mid: '+',
+                right_mid: '+',
+            }),
+            TableStyle::Unicode => Some(BorderChars {
+                horizontal: '─',
+                vertical: '│',
+                cross: '┼',
+                tee_down: '┬',
+                top_left: '┌',
+                top_mid: '┬',
+                top_right: '┐',
+                bottom_left: '└',
+                bottom_mid: '┴',
+                bottom_right: '┘',
+                left_mid: '├',
+                right_mid: '┤',
+            }),
+        }
+    }
+}
+
+/// Render a horizontal rule spanning `widths`, using `mid` at internal boundaries.
+fn border_rule(widths: &[usize], left: char, mid: char, right: char, fill: char) -> String {
+    let segments: Vec<String> = widths.iter().map(|&w| fill.to_string().repeat(w)).collect();
+    format!("{left}{}{right}", segments.join(&mid.to_string()))
+}
+
+/// Render a content row from already-justified cell strings, bordered with `vertical`.
+fn border_row(cells: &[String], vertical: char) -> String {
+    format!("{vertical}{}{vertical}", cells.join(&vertical.to_string()))
+}
+
+/// Resolve a footer row's (text, span) cells to (text, width) pairs, where `width` is the
+/// combined width of the spanned columns plus their inter-column separators — the same
+/// arithmetic `print` already uses for group spans, generalized to an arbitrary column range.
+fn footer_cell_widths(widths: &[usize], cells: &[(String, usize)]) -> Vec<(String, usize)> {
+    let mut col = 0;
+    cells
+        .iter()
+        .map(|(text, span)| {
+            let span = (*span).max(1);
+            let w = widths[col..col + span].iter().sum::<usize>() + span - 1;
+            col += span;
+            (text.clone(), w)
+        })
+        .collect()
+}
+
+/// Justify a footer cell: right for cells whose text parses as a number (matching data-row
+/// alignment), left otherwise (matching header alignment).
+fn justify_footer_cell(text: &str, width: usize) -> String {
+    if text.trim().parse::<f64>().is_ok() {
+        rjust(text, width)
+    } else {
+        ljust(text, width)
+    }
+}
+
+/// Render a horizontal rule over `widths` where each internal junction character is chosen by
+/// `junction_at(col_index)`, given the 1-based index of the column boundary.
+fn border_rule_with_junctions(
+    widths: &[usize],
+    left: char,
+    right: char,
+    fill: char,
+    junction_at: impl Fn(usize) -> char,
+) -> String {
+    let mut line = String::new();
+    line.push(left);
+    for (i, &w) in widths.iter().enumerate() {
+        line.push_str(&fill.to_string().repeat(w));
+        if i + 1 < widths.len() {
+            line.push(junction_at(i + 1));
+        }
+    }
+    line.push(right);
+    line
+}
+
+/// A column recovered by [`parse_fixed_width`] from whitespace-aligned tabular text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedColumn {
+    pub header: Option<String>,
+    pub values: Vec<String>,
+}
+
+/// Find the `(start, end)` ranges of maximal non-blank runs in `chars`, i.e. the inverse of
+/// the blank columns.
+fn non_blank_runs(chars: &[char]) -> Vec<(usize, usize)> {
+    let mut runs = Vec::new();
+    let mut start = None;
+    for (i, &c) in chars.iter().enumerate() {
+        if c != ' ' {
+            start.get_or_insert(i);
+        } else if let Some(s) = start.take() {
+            runs.push((s, i));
+        }
+    }
+    if let Some(s) = start {
+        runs.push((s, chars.len()));
+    }
+    runs
+}
+
+/// Parse whitespace-aligned tabular text (e.g. this crate's own `Table::print` output, or
+/// third-party command output) back into columns.
+///
+/// Column boundaries are inferred with the histogram approach `guess-width`-style tools use:
+/// each line contributes the character positions it occupies to a per-position occupancy
+/// count, and boundaries fall at positions that are blank across every line (troughs),
+/// flanked by occupied runs. When `has_header` is set, the header line's own word-start
+/// positions anchor the boundaries instead, which also handles data values that are wider
+/// than their header and would otherwise make a naive histogram merge two columns.
+pub fn parse_fixed_width(text: &str, has_header: bool) -> Vec<ParsedColumn> {
+    let lines: Vec<Vec<char>> = text
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.chars().collect())
+        .collect();
+    if lines.is_empty() {
+        return Vec::new();
+    }
+
+    let field_at = |chars: &[char], start: usize, end: usize| -> String {
+        chars
+            .get(start..end.min(chars.len()))
+            .unwrap_or(&[])
+            .iter()
+            .collect::<String>()
+            .trim()
+            .to_string()
+    };
+
+    let (header_line, data_lines) = if has_header {
+        (Some(&lines[0]), &lines[1..])
+    } else {
+        (None, &lines[..])
+    };
+
+    // Field boundaries as (start, end) ranges, end == usize::MAX meaning "to end of line".
+    let boundaries: Vec<(usize, usize)> = if let Some(header) = header_line {
+        let starts: Vec<(usize, usize)> = non_blank_runs(header);
+        starts
+            .iter()
+            .enumerate()
+            .map(|(i, &(start, _))| {
+                let end = starts.get(i + 1).map(|&(s, _)| s).unwrap_or(usize::MAX);
+                (start, end)
+            })
+            .collect()
+    } else {
+        let max_len = lines.iter().map(|l| l.len()).max().unwrap_or(0);
+        let mut occupied = vec![false; max_len];
+        for line in &lines {
+            for (i, &c) in line.iter().enumerate() {
+                if c != ' ' {
+                    occupied[i] = true;
+                }
+            }
+        }
+        let markers: Vec<char> = occupied
+            .iter()
+            .map(|&o| if o { 'x' } else { ' ' })
+            .collect();
+        non_blank_runs(&markers)
+    };
+
+    let headers: Vec<Option<String>> = match header_line {
+        Some(header) => boundaries
+            .iter()
+            .map(|&(start, end)| Some(field_at(header, start, end)))
+            .collect(),
+        None => vec![None; boundaries.len()],
+    };
+
+    let columns: Vec<Vec<String>> = boundaries
+        .iter()
+        .map(|&(start, end)| {
+            data_lines
+                .iter()
+                .map(|line| field_at(line, start, end))
+                .collect()
+        })
+        .collect();
+
+    headers
+        .into_iter()
+        .zip(columns)
+        .map(|(header, values)| ParsedColumn { header, values })
+        .collect()
+}
+
+/// Precomputed layout shared by `Table::render_bare` and `Table::render_bordered`, bundled into
+/// one struct so adding a render mode doesn't grow either function's argument list.
+#[derive(Clone, Copy)]
+struct RenderLayout<'a> {
+    widths: &'a [usize],
+    group_info: &'a [((usize, usize), usize)],
+    groups: &'a [(Option<&'a str>, usize)],
+    has_named_groups: bool,
+    rows: &'a [Vec<Vec<&'a str>>],
+    footer_rows: &'a [Vec<(String, usize)>],
+}
+
 /// A builder for aligned tabular output with optional column grouping.
 ///
 /// Columns are added with `column()`, optionally organized under named groups
 /// using `group()`. Call `print()` to output the formatted table.
+#[derive(Default)]
 pub struct Table {
     columns: Vec<Column>,
     groups: Vec<Group>,
@@ -22,17 +351,43 @@ pub struct Table {
     // belong to current_group_name (which may be None for ungrouped columns).
     current_group_start: usize,
     current_group_name: Option<String>,
+    // Budget for the total rendered width. `None` (the default) keeps the unbounded
+    // behavior of printing natural widths regardless of terminal size.
+    max_width: Option<usize>,
+    style: TableStyle,
+    // Trailing rows rendered below the data, each a list of (text, span) cells where `span`
+    // is the number of adjacent columns (plus their separators) the cell's width covers.
+    footer_rows: Vec<Vec<(String, usize)>>,
 }
 
 impl Table {
     /// Create an empty table.
     pub fn new() -> Self {
-        Table {
-            columns: Vec::new(),
-            groups: Vec::new(),
-            current_group_start: 0,
-            current_group_name: None,
-        }
+        Table::default()
+    }
+
+    /// Add a full-width footer row below the data, made of cells that each span `n` adjacent
+    /// columns (e.g. a "total" or note row that doesn't fit the rigid one-value-per-column
+    /// grid). The `span` values across a row must sum to the table's column count. A cell
+    /// whose text parses as a number is right-justified like data; otherwise it's
+    /// left-justified like a header.
+    pub fn footer_row(mut self, cells: Vec<(String, usize)>) -> Self {
+        self.footer_rows.push(cells);
+        self
+    }
+
+    /// Set the border/layout style (default: `TableStyle::Bare`, the original whitespace layout).
+    pub fn style(mut self, style: TableStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Set a total-width budget: when the natural layout would exceed it, the widest columns
+    /// are shrunk and their contents word-wrapped to fit. `None` (the default) prints natural
+    /// widths unconditionally, which is what scripted/non-tty callers generally want.
+    pub fn max_width(mut self, max_width: Option<usize>) -> Self {
+        self.max_width = max_width;
+        self
     }
 
     /// Add a column with the given header and data rows.
@@ -74,22 +429,68 @@ impl Table {
 
     /// Print the table to stdout with aligned columns.
     pub fn print(&self) {
+        for line in self.render_lines() {
+            println!("{line}");
+        }
+    }
+
+    /// Render the table to the lines `print` would emit, without printing them. Used by `print`
+    /// itself and by tests that need to inspect the actual rendered output.
+    fn render_lines(&self) -> Vec<String> {
         if self.columns.is_empty() {
-            return;
+            return Vec::new();
         }
 
         let groups: Vec<_> = self.all_groups().collect();
 
-        // Base column widths: max of header and data widths (using Unicode width)
-        let widths: Vec<usize> = self
+        // Base column widths: max of header and data widths, where a multi-line cell's
+        // width is the widest of its individual lines.
+        let mut widths: Vec<usize> = self
             .columns
             .iter()
             .map(|col| {
-                let max_data = col.data.iter().map(|v| v.width()).max().unwrap_or(0);
-                std::cmp::max(col.header.width(), max_data)
+                let max_data = col
+                    .data
+                    .iter()
+                    .flat_map(|v| v.lines())
+                    .map(display_width)
+                    .max()
+                    .unwrap_or(0);
+                std::cmp::max(display_width(&col.header), max_data)
             })
             .collect();
 
+        // If a width budget was set and the natural layout doesn't fit, shrink the widest
+        // columns one column-width at a time until it does (or all columns are at the floor),
+        // then word-wrap the shrunk columns' contents to their new width.
+        let mut wrapped: Vec<Option<Vec<String>>> = vec![None; self.columns.len()];
+        if let Some(budget) = self.max_width {
+            const MIN_COLUMN_WIDTH: usize = 3;
+            let sep_width = self.columns.len().saturating_sub(1);
+            while widths.iter().sum::<usize>() + sep_width > budget {
+                let Some((idx, &w)) = widths
+                    .iter()
+                    .enumerate()
+                    .filter(|&(_, &w)| w > MIN_COLUMN_WIDTH)
+                    .max_by_key(|&(_, &w)| w)
+                else {
+                    break;
+                };
+                widths[idx] = (w - 1).max(MIN_COLUMN_WIDTH);
+            }
+            for (idx, col) in self.columns.iter().enumerate() {
+                let w = widths[idx];
+                if col.data.iter().any(|v| display_width(v) > w) {
+                    wrapped[idx] = Some(col.data.iter().map(|v| word_wrap(v, w)).collect());
+                }
+            }
+        }
+        let cell_data = |col_idx: usize| -> &[String] {
+            wrapped[col_idx]
+                .as_deref()
+                .unwrap_or(&self.columns[col_idx].data)
+        };
+
         let has_named_groups = groups.iter().any(|(name, _)| name.is_some());
 
         // Precompute column ranges and target span widths for each group. When a group name is
@@ -104,7 +505,7 @@ impl Table {
                     col += count;
                     let natural_span = widths[start..col].iter().sum::<usize>() + count - 1;
                     let target_span = if has_named_groups {
-                        let name_width = name.map(|n| n.width()).unwrap_or(0);
+                        let name_width = name.map(display_width).unwrap_or(0);
                         std::cmp::max(natural_span, name_width)
                     } else {
                         natural_span
@@ -114,17 +515,63 @@ impl Table {
                 .collect()
         };
 
+        // Row heights: the max line count across a row's cells, so multi-line cells pad
+        // shorter neighbors with blank lines.
+        let num_rows = self.columns[0].data.len();
+        let rows: Vec<Vec<Vec<&str>>> = (0..num_rows)
+            .map(|row_idx| {
+                (0..self.columns.len())
+                    .map(|col_idx| match cell_data(col_idx).get(row_idx) {
+                        Some(val) => val.lines().collect(),
+                        None => vec!["-"],
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let footer_rows: Vec<Vec<(String, usize)>> = self
+            .footer_rows
+            .iter()
+            .map(|cells| footer_cell_widths(&widths, cells))
+            .collect();
+
+        let layout = RenderLayout {
+            widths: &widths,
+            group_info: &group_info,
+            groups: &groups,
+            has_named_groups,
+            rows: &rows,
+            footer_rows: &footer_rows,
+        };
+
+        match self.style.chars() {
+            None => self.render_bare(&layout),
+            Some(chars) => self.render_bordered(&layout, &chars),
+        }
+    }
+
+    /// Render using the original space-separated, trailing-whitespace-trimmed layout, as the
+    /// lines `print` would emit.
+    fn render_bare(&self, layout: &RenderLayout) -> Vec<String> {
+        let RenderLayout {
+            widths,
+            group_info,
+            groups,
+            has_named_groups,
+            rows,
+            footer_rows,
+        } = *layout;
+        let mut lines = Vec::new();
+
         if has_named_groups {
-            // Print group header row
             let header: Vec<String> = groups
                 .iter()
-                .zip(&group_info)
+                .zip(group_info)
                 .map(|(&(name, _), &(_, span))| ljust(name.unwrap_or(""), span))
                 .collect();
-            println!("{}", header.join(" ").trim_ascii_end());
+            lines.push(header.join(" ").trim_ascii_end().to_string());
         }
 
-        // Print column headers (left-justified), with inter-group padding
         let header_line: Vec<String> = group_info
             .iter()
             .map(|&((start, end), span)| {
@@ -136,33 +583,169 @@ impl Table {
                 ljust(&cols.join(" "), span)
             })
             .collect();
-        println!("{}", header_line.join(" ").trim_ascii_end());
+        lines.push(header_line.join(" ").trim_ascii_end().to_string());
 
-        // Print data rows (right-justified for numeric alignment), with inter-group padding
-        let num_rows = self.columns[0].data.len();
-        for row_idx in 0..num_rows {
-            let row: Vec<String> = group_info
+        for cell_lines in rows {
+            let row_height = cell_lines
                 .iter()
-                .map(|&((start, end), span)| {
-                    let vals: Vec<String> = self.columns[start..end]
-                        .iter()
-                        .zip(&widths[start..end])
-                        .map(|(col, &w)| {
-                            let val = col.data.get(row_idx).map(|s| s.as_str()).unwrap_or("-");
-                            rjust(val, w)
-                        })
-                        .collect();
-                    ljust(&vals.join(" "), span)
-                })
+                .map(|lines| lines.len())
+                .max()
+                .unwrap_or(1)
+                .max(1);
+            for line_idx in 0..row_height {
+                let row: Vec<String> = group_info
+                    .iter()
+                    .map(|&((start, end), span)| {
+                        let vals: Vec<String> = cell_lines[start..end]
+                            .iter()
+                            .zip(&widths[start..end])
+                            .map(|(lines, &w)| rjust(lines.get(line_idx).copied().unwrap_or(""), w))
+                            .collect();
+                        ljust(&vals.join(" "), span)
+                    })
+                    .collect();
+                lines.push(row.join(" ").trim_ascii_end().to_string());
+            }
+        }
+
+        for cells in footer_rows {
+            let row: Vec<String> = cells
+                .iter()
+                .map(|(text, w)| justify_footer_cell(text, *w))
+                .collect();
+            lines.push(row.join(" ").trim_ascii_end().to_string());
+        }
+
+        lines
+    }
+
+    /// Render with box-drawing borders: vertical separators between columns, a rule under the
+    /// header, and named groups as a spanning top row whose borders bracket exactly their
+    /// member columns. Returns the lines `print` would emit.
+    fn render_bordered(&self, layout: &RenderLayout, chars: &BorderChars) -> Vec<String> {
+        let RenderLayout {
+            widths,
+            group_info,
+            groups,
+            has_named_groups,
+            rows,
+            footer_rows,
+        } = *layout;
+        let mut lines = Vec::new();
+
+        // Column indices at which a new group starts (always includes 0 and num_columns).
+        let group_edges: std::collections::HashSet<usize> = {
+            let mut col = 0;
+            let mut edges: std::collections::HashSet<usize> = [0, self.columns.len()].into();
+            for &(_, count) in groups {
+                col += count;
+                edges.insert(col);
+            }
+            edges
+        };
+        let junction_at = |pos: usize| -> char {
+            if group_edges.contains(&pos) {
+                chars.cross
+            } else {
+                chars.tee_down
+            }
+        };
+
+        if has_named_groups {
+            let group_spans: Vec<usize> = group_info.iter().map(|&(_, span)| span).collect();
+            lines.push(border_rule(
+                &group_spans,
+                chars.top_left,
+                chars.top_mid,
+                chars.top_right,
+                chars.horizontal,
+            ));
+            let group_cells: Vec<String> = groups
+                .iter()
+                .zip(group_info)
+                .map(|(&(name, _), &(_, span))| ljust(name.unwrap_or(""), span))
+                .collect();
+            lines.push(border_row(&group_cells, chars.vertical));
+
+            lines.push(border_rule_with_junctions(
+                widths,
+                chars.left_mid,
+                chars.right_mid,
+                chars.horizontal,
+                junction_at,
+            ));
+        } else {
+            lines.push(border_rule(
+                widths,
+                chars.top_left,
+                chars.top_mid,
+                chars.top_right,
+                chars.horizontal,
+            ));
+        }
+
+        let header_cells: Vec<String> = self
+            .columns
+            .iter()
+            .zip(widths)
+            .map(|(c, &w)| ljust(&c.header, w))
+            .collect();
+        lines.push(border_row(&header_cells, chars.vertical));
+        lines.push(border_rule(
+            widths,
+            chars.left_mid,
+            chars.cross,
+            chars.right_mid,
+            chars.horizontal,
+        ));
+
+        for cell_lines in rows {
+            let row_height = cell_lines
+                .iter()
+                .map(|lines| lines.len())
+                .max()
+                .unwrap_or(1)
+                .max(1);
+            for line_idx in 0..row_height {
+                let cells: Vec<String> = cell_lines
+                    .iter()
+                    .zip(widths)
+                    .map(|(lines, &w)| rjust(lines.get(line_idx).copied().unwrap_or(""), w))
+                    .collect();
+                lines.push(border_row(&cells, chars.vertical));
+            }
+        }
+
+        for cells in footer_rows {
+            lines.push(border_rule(
+                widths,
+                chars.left_mid,
+                chars.cross,
+                chars.right_mid,
+                chars.horizontal,
+            ));
+            let row: Vec<String> = cells
+                .iter()
+                .map(|(text, w)| justify_footer_cell(text, *w))
                 .collect();
-            println!("{}", row.join(" ").trim_ascii_end());
+            lines.push(border_row(&row, chars.vertical));
         }
+
+        lines.push(border_rule(
+            widths,
+            chars.bottom_left,
+            chars.bottom_mid,
+            chars.bottom_right,
+            chars.horizontal,
+        ));
+
+        lines
     }
 }
 
 /// Left-justify string to given width (using Unicode display width).
 fn ljust(s: &str, width: usize) -> String {
-    let current_width = s.width();
+    let current_width = display_width(s);
     if current_width >= width {
         s.to_string()
     } else {
@@ -172,10 +755,161 @@ fn ljust(s: &str, width: usize) -> String {
 
 /// Right-justify string to given width (using Unicode display width).
 fn rjust(s: &str, width: usize) -> String {
-    let current_width = s.width();
+    let current_width = display_width(s);
     if current_width >= width {
         s.to_string()
     } else {
         format!("{}{}", " ".repeat(width - current_width), s)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_fixed_width_round_trips_table_print_output() {
+        let table = Table::new()
+            .column("city", vec!["London".into(), "Zagreb".into()])
+            .column("temp", vec!["12.5".into(), "21.0".into()]);
+        let rendered = table.render_lines().join("\n");
+
+        let columns = parse_fixed_width(&rendered, true);
+        assert_eq!(columns.len(), 2);
+        assert_eq!(columns[0].header.as_deref(), Some("city"));
+        assert_eq!(columns[0].values, vec!["London", "Zagreb"]);
+        assert_eq!(columns[1].header.as_deref(), Some("temp"));
+        assert_eq!(columns[1].values, vec!["12.5", "21.0"]);
+    }
+
+    #[test]
+    fn parse_fixed_width_round_trips_wider_data_than_header() {
+        // The header line alone wouldn't place a boundary between "id"/"name" correctly if
+        // naive word-start anchoring didn't also account for values wider than their header.
+        let table = Table::new()
+            .column("id", vec!["1".into(), "2".into()])
+            .column("name", vec!["Alexandria".into(), "Christchurch".into()]);
+        let rendered = table.render_lines().join("\n");
+
+        let columns = parse_fixed_width(&rendered, true);
+        assert_eq!(columns.len(), 2);
+        assert_eq!(columns[0].values, vec!["1", "2"]);
+        assert_eq!(columns[1].values, vec!["Alexandria", "Christchurch"]);
+    }
+
+    #[test]
+    fn non_blank_runs_finds_maximal_runs() {
+        let chars: Vec<char> = "  ab  cde  ".chars().collect();
+        assert_eq!(non_blank_runs(&chars), vec![(2, 4), (6, 9)]);
+    }
+
+    #[test]
+    fn word_wrap_breaks_at_width() {
+        let wrapped = word_wrap("the quick brown fox jumps", 10);
+        assert_eq!(wrapped, "the quick\nbrown fox\njumps");
+    }
+
+    #[test]
+    fn word_wrap_leaves_overlong_word_on_its_own_line() {
+        let wrapped = word_wrap("a supercalifragilisticexpialidocious word", 10);
+        assert_eq!(wrapped, "a\nsupercalifragilisticexpialidocious\nword");
+    }
+
+    #[test]
+    fn word_wrap_short_text_is_unchanged() {
+        assert_eq!(word_wrap("short", 10), "short");
+    }
+
+    #[test]
+    fn max_width_shrinks_widest_column_and_wraps_its_contents() {
+        let table = Table::new()
+            .column("id", vec!["1".into()])
+            .column(
+                "description",
+                vec!["a very long description that needs wrapping".into()],
+            )
+            .max_width(Some(20));
+        let lines = table.render_lines();
+
+        assert!(lines.iter().all(|line| display_width(line) <= 20));
+        // The short column keeps its natural width; only "description" shrank and wrapped.
+        assert!(lines.iter().any(|line| line.starts_with("id ")));
+    }
+
+    #[test]
+    fn max_width_does_not_shrink_columns_that_already_fit() {
+        let table = Table::new()
+            .column("a", vec!["1".into()])
+            .column("b", vec!["2".into()])
+            .max_width(Some(80));
+        let lines = table.render_lines();
+
+        assert_eq!(lines, vec!["a b", "1 2"]);
+    }
+
+    #[test]
+    fn ascii_style_draws_box_borders() {
+        let table = Table::new()
+            .column("a", vec!["1".into()])
+            .column("b", vec!["22".into()])
+            .style(TableStyle::Ascii);
+        let lines = table.render_lines();
+
+        assert_eq!(lines, vec!["+-+--+", "|a|b |", "+-+--+", "|1|22|", "+-+--+"]);
+    }
+
+    #[test]
+    fn unicode_style_draws_box_borders() {
+        let table = Table::new()
+            .column("a", vec!["1".into()])
+            .column("b", vec!["22".into()])
+            .style(TableStyle::Unicode);
+        let lines = table.render_lines();
+
+        assert_eq!(
+            lines,
+            vec!["┌─┬──┐", "│a│b │", "├─┼──┤", "│1│22│", "└─┴──┘"]
+        );
+    }
+
+    #[test]
+    fn ascii_style_named_group_spans_its_member_columns() {
+        let table = Table::new()
+            .group("weather")
+            .column("temp", vec!["1".into()])
+            .column("wind", vec!["2".into()])
+            .style(TableStyle::Ascii);
+        let lines = table.render_lines();
+
+        assert_eq!(lines[0], "+---------+");
+        assert_eq!(lines[1], "|weather  |");
+        assert_eq!(lines[2], "+----+----+");
+        assert_eq!(lines[3], "|temp|wind|");
+    }
+
+    #[test]
+    fn footer_row_right_justifies_numeric_text() {
+        let table = Table::new()
+            .column("city", vec!["London".into(), "Zagreb".into()])
+            .column("temp", vec!["12.5".into(), "21.0".into()])
+            .footer_row(vec![("avg".into(), 1), ("16.75".into(), 1)]);
+        let lines = table.render_lines();
+
+        // "avg" isn't numeric so it's left-justified like a header; "16.75" is, so it's
+        // right-justified like the data column above it.
+        assert_eq!(lines.last().unwrap(), "avg    16.75");
+    }
+
+    #[test]
+    fn footer_row_spans_multiple_columns() {
+        let table = Table::new()
+            .column("city", vec!["London".into()])
+            .column("temp", vec!["12.5".into()])
+            .column("wind", vec!["5".into()])
+            .footer_row(vec![("note: forecast only".into(), 3)]);
+        let lines = table.render_lines();
+
+        // A single cell spanning all 3 columns covers their combined width plus separators.
+        assert_eq!(lines.last().unwrap(), "note: forecast only");
+    }
+}
@@ -0,0 +1,1385 @@
+use std::sync::LazyLock;
+
+use chrono::{
+    DateTime, Datelike, Duration, FixedOffset, LocalResult, NaiveDate, NaiveDateTime, NaiveTime,
+    TimeZone, Weekday,
+};
+use chrono_tz::Tz;
+use regex::Regex;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RequestedDate {
+    Today,
+    Tomorrow,
+    RelativeDays(u8),
+    Weekday(Weekday),
+    Absolute(NaiveDate),
+    /// An absolute instant carrying its own UTC offset (e.g. from RFC3339
+    /// input), used as-is instead of being re-interpreted in the target zone.
+    Instant(DateTime<FixedOffset>),
+    /// Another date offset by a fixed number of days (positive or negative).
+    /// Used for count-based ranges like "next 7 days" and ISO 8601 duration
+    /// intervals like "2025-01-15/P3D".
+    OffsetDays(Box<RequestedDate>, i64),
+    /// A weekday within the week that is `weeks_offset` ISO weeks (starting
+    /// Monday) from the current week. `weeks_offset` 0 is this week, 1 is
+    /// next week. Used for "this week"/"next week".
+    RelativeWeek {
+        weeks_offset: i32,
+        weekday: Weekday,
+    },
+    /// The first occurrence of `weekday` strictly after `relative_to`.
+    /// Used for "next monday".
+    NextWeekday(Weekday),
+    /// The most recent occurrence of `weekday` strictly before `relative_to`.
+    /// Used for "last friday".
+    LastWeekday(Weekday),
+    /// The upcoming Saturday (inclusive of today). Its range end extends
+    /// through the following Sunday, making "weekend"/"this weekend" a
+    /// two-day span.
+    Weekend,
+    /// A day and month, resolved in the current year and rolled to next
+    /// year if that date has already passed. Used for "July 4"/"4 July".
+    MonthDay {
+        month: u32,
+        day: u32,
+    },
+}
+
+/// Parse an absolute instant that carries its own UTC offset: either full
+/// RFC3339 (`2025-10-13T23:00:00-08:00`) or a bare date with a trailing
+/// offset (`2025-10-13-08:00`).
+fn parse_instant(s: &str) -> Option<DateTime<FixedOffset>> {
+    if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+        return Some(dt);
+    }
+    let (date_part, offset_part) = s.split_at_checked(10)?;
+    if offset_part.starts_with('+') || offset_part.starts_with('-') || offset_part == "Z" {
+        DateTime::parse_from_rfc3339(&format!("{date_part}T00:00:00{offset_part}")).ok()
+    } else {
+        None
+    }
+}
+
+fn parse_weekday(s: &str) -> Option<Weekday> {
+    match s {
+        "mon" | "monday" => Some(Weekday::Mon),
+        "tue" | "tuesday" => Some(Weekday::Tue),
+        "wed" | "wednesday" => Some(Weekday::Wed),
+        "thu" | "thursday" => Some(Weekday::Thu),
+        "fri" | "friday" => Some(Weekday::Fri),
+        "sat" | "saturday" => Some(Weekday::Sat),
+        "sun" | "sunday" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+/// Parse a `this`/`next`/`last` modifier applied to a weekday name, e.g.
+/// "next monday" or "last fri". "this" keeps the unmodified `Weekday`
+/// semantics (today's next occurrence, inclusive of today).
+fn parse_modified_weekday(s: &str) -> Option<RequestedDate> {
+    let (modifier, rest) = s.split_once(' ')?;
+    let weekday = parse_weekday(rest)?;
+    match modifier {
+        "this" => Some(RequestedDate::Weekday(weekday)),
+        "next" => Some(RequestedDate::NextWeekday(weekday)),
+        "last" => Some(RequestedDate::LastWeekday(weekday)),
+        _ => None,
+    }
+}
+
+/// Parse `in N days`/`N days ago`, mirroring the `+N` form but allowing
+/// the past via "ago".
+fn parse_relative_count(s: &str) -> Option<RequestedDate> {
+    fn parse_days(s: &str) -> Option<i64> {
+        s.strip_suffix(" days")
+            .or_else(|| s.strip_suffix(" day"))?
+            .parse()
+            .ok()
+    }
+    if let Some(rest) = s.strip_prefix("in ") {
+        let days = parse_days(rest)?;
+        Some(RequestedDate::OffsetDays(
+            Box::new(RequestedDate::Today),
+            days,
+        ))
+    } else if let Some(rest) = s.strip_suffix(" ago") {
+        let days = parse_days(rest)?;
+        Some(RequestedDate::OffsetDays(
+            Box::new(RequestedDate::Today),
+            -days,
+        ))
+    } else {
+        None
+    }
+}
+
+fn parse_month_name(s: &str) -> Option<u32> {
+    match s {
+        "jan" | "january" => Some(1),
+        "feb" | "february" => Some(2),
+        "mar" | "march" => Some(3),
+        "apr" | "april" => Some(4),
+        "may" => Some(5),
+        "jun" | "june" => Some(6),
+        "jul" | "july" => Some(7),
+        "aug" | "august" => Some(8),
+        "sep" | "sept" | "september" => Some(9),
+        "oct" | "october" => Some(10),
+        "nov" | "november" => Some(11),
+        "dec" | "december" => Some(12),
+        _ => None,
+    }
+}
+
+/// Parse a day number, optionally followed by an ordinal suffix ("4th", "1st").
+fn parse_day_number(s: &str) -> Option<u32> {
+    let digits_len = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    if digits_len == 0 {
+        return None;
+    }
+    match &s[digits_len..] {
+        "" | "st" | "nd" | "rd" | "th" => s[..digits_len].parse().ok(),
+        _ => None,
+    }
+}
+
+/// Parse a month-and-day form in either order: "july 4", "4 july", "nov 5th".
+fn parse_month_day(s: &str) -> Option<RequestedDate> {
+    let (a, b) = s.split_once(' ')?;
+    let (month, day) = match (parse_month_name(a), parse_month_name(b)) {
+        (Some(month), _) => (month, parse_day_number(b)?),
+        (_, Some(month)) => (month, parse_day_number(a)?),
+        _ => return None,
+    };
+    NaiveDate::from_ymd_opt(2000, month, day)?; // validate the day exists in this month
+    Some(RequestedDate::MonthDay { month, day })
+}
+
+fn parse_date_only(s: &str) -> anyhow::Result<RequestedDate> {
+    use anyhow::Context;
+    if let Some(instant) = parse_instant(s) {
+        return Ok(RequestedDate::Instant(instant));
+    }
+    let s = s.to_lowercase();
+    match s.as_str() {
+        "today" => Ok(RequestedDate::Today),
+        "tomorrow" => Ok(RequestedDate::Tomorrow),
+        "weekend" | "this weekend" => Ok(RequestedDate::Weekend),
+        _ => {
+            if let Some(weekday) = parse_weekday(&s) {
+                Ok(RequestedDate::Weekday(weekday))
+            } else if let Some(date) = parse_modified_weekday(&s) {
+                Ok(date)
+            } else if let Some(days) = s.strip_prefix('+').and_then(|n| n.parse::<u8>().ok()) {
+                Ok(RequestedDate::RelativeDays(days))
+            } else if let Some(days) = s.strip_prefix('-').and_then(|n| n.parse::<i64>().ok()) {
+                Ok(RequestedDate::OffsetDays(
+                    Box::new(RequestedDate::Today),
+                    -days,
+                ))
+            } else if let Some(date) = parse_relative_count(&s) {
+                Ok(date)
+            } else if let Some(date) = parse_month_day(&s) {
+                Ok(date)
+            } else {
+                NaiveDate::parse_from_str(&s, "%Y-%m-%d")
+                    .map(RequestedDate::Absolute)
+                    .context("dates must be YYYY-MM-DD, +N, weekday name, 'today' or 'tomorrow'")
+            }
+        }
+    }
+}
+
+/// Parse a bare clock time: `6`/`18` (hour only), `HH:MM`, or the words
+/// `noon`/`midnight`.
+fn parse_time_of_day(s: &str) -> Option<NaiveTime> {
+    match s {
+        "noon" => return NaiveTime::from_hms_opt(12, 0, 0),
+        "midnight" => return NaiveTime::from_hms_opt(0, 0, 0),
+        _ => {}
+    }
+    if s.bytes().all(|b| b.is_ascii_digit()) {
+        if let Ok(hour) = s.parse::<u32>() {
+            return NaiveTime::from_hms_opt(hour, 0, 0);
+        }
+    }
+    NaiveTime::parse_from_str(s, "%H:%M").ok()
+}
+
+/// Parse a date with an optional `@time` suffix, e.g. `tomorrow@09:00`.
+fn parse_date(s: &str) -> anyhow::Result<(RequestedDate, Option<NaiveTime>)> {
+    use anyhow::Context;
+    match s.split_once('@') {
+        Some((date_part, time_part)) => {
+            let date = parse_date_only(date_part)?;
+            let time = parse_time_of_day(time_part)
+                .with_context(|| format!("invalid time of day: '{time_part}'"))?;
+            Ok((date, Some(time)))
+        }
+        None => Ok((parse_date_only(s)?, None)),
+    }
+}
+
+/// Parse one side of a `date1..date2` range: a date, optionally followed
+/// by a space and a bare time of day (`today 6`, `fri 20`). A side that is
+/// only a time of day (no date, e.g. the `18` in `today 6..18`) inherits
+/// its date from the other side of the range.
+fn parse_date_and_time(s: &str) -> anyhow::Result<(Option<RequestedDate>, Option<NaiveTime>)> {
+    if let Some((date_part, time_part)) = s.rsplit_once(' ') {
+        if let (Ok(date), Some(time)) = (parse_date_only(date_part), parse_time_of_day(time_part)) {
+            return Ok((Some(date), Some(time)));
+        }
+    }
+    if let Some(time) = parse_time_of_day(s) {
+        return Ok((None, Some(time)));
+    }
+    let (date, time) = parse_date(s)?;
+    Ok((Some(date), time))
+}
+
+/// Matches `next N days` / `last N days` (case-insensitive), a count-based
+/// window relative to "today" rather than a pair of explicit endpoints.
+static COUNT_DAYS_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)^(next|last)\s+(\d+)\s+days?$").unwrap());
+
+/// Parse `next N days` / `last N days` into a count-based window anchored
+/// on "today": `next 7 days` spans today through six days from now,
+/// `last 3 days` spans two days ago through today.
+fn parse_count_days(s: &str) -> Option<(RequestedDate, RequestedDate)> {
+    let caps = COUNT_DAYS_RE.captures(s)?;
+    let span: i64 = caps[2].parse::<i64>().ok()?.checked_sub(1)?;
+    let offset_today = || RequestedDate::OffsetDays(Box::new(RequestedDate::Today), span);
+    if caps[1].eq_ignore_ascii_case("next") {
+        Some((RequestedDate::Today, offset_today()))
+    } else {
+        Some((
+            RequestedDate::OffsetDays(Box::new(RequestedDate::Today), -span),
+            RequestedDate::Today,
+        ))
+    }
+}
+
+/// Build the Monday..Sunday range for the week `weeks_offset` ISO weeks
+/// from the current one (0 = this week, 1 = next week).
+fn week_range(weeks_offset: i32) -> (RequestedDate, RequestedDate) {
+    (
+        RequestedDate::RelativeWeek {
+            weeks_offset,
+            weekday: Weekday::Mon,
+        },
+        RequestedDate::RelativeWeek {
+            weeks_offset,
+            weekday: Weekday::Sun,
+        },
+    )
+}
+
+/// Parse the fixed `this week` / `next week` phrases. "weekend" is handled
+/// by `parse_date_only`'s `RequestedDate::Weekend`, whose range-widening lives
+/// in `resolve_time_range`.
+fn parse_named_range(s: &str) -> Option<(RequestedDate, RequestedDate)> {
+    match s.to_lowercase().as_str() {
+        "this week" => Some(week_range(0)),
+        "next week" => Some(week_range(1)),
+        _ => None,
+    }
+}
+
+/// Parse an ISO 8601 duration of the form `PnD` (n days) into a day count.
+fn parse_duration_days(s: &str) -> Option<i64> {
+    s.strip_prefix('P')?
+        .strip_suffix('D')?
+        .parse::<i64>()
+        .ok()
+        .filter(|&n| n > 0)
+}
+
+/// Parse an ISO 8601 interval: either two dates (`2025-01-15/2025-01-20`)
+/// or a start date and a duration (`2025-01-15/P3D`).
+fn parse_iso_interval(start: &str, rest: &str) -> anyhow::Result<(RequestedDate, RequestedDate)> {
+    let start_date = parse_date_only(start)?;
+    let end_date = match parse_duration_days(rest) {
+        Some(days) => RequestedDate::OffsetDays(Box::new(start_date.clone()), days - 1),
+        None => parse_date_only(rest)?,
+    };
+    Ok((start_date, end_date))
+}
+
+/// A parsed `dates` argument: a start/end date pair, plus optional
+/// explicit clock-time bounds for restricting the range to part of a day
+/// (e.g. `today 6..18` or `tomorrow@09:00..tomorrow@21:00`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct DateRange {
+    pub start_date: RequestedDate,
+    pub end_date: RequestedDate,
+    pub start_clock: Option<NaiveTime>,
+    pub end_clock: Option<NaiveTime>,
+}
+
+impl DateRange {
+    fn whole_days(start_date: RequestedDate, end_date: RequestedDate) -> Self {
+        DateRange {
+            start_date,
+            end_date,
+            start_clock: None,
+            end_clock: None,
+        }
+    }
+}
+
+pub fn parse_date_range(s: &str) -> anyhow::Result<DateRange> {
+    if let Some((start_date, end_date)) = parse_count_days(s) {
+        return Ok(DateRange::whole_days(start_date, end_date));
+    }
+    if let Some((start_date, end_date)) = parse_named_range(s) {
+        return Ok(DateRange::whole_days(start_date, end_date));
+    }
+    if let Some(pos) = s.find('/') {
+        let (start_date, end_date) = parse_iso_interval(&s[..pos], &s[pos + 1..])?;
+        return Ok(DateRange::whole_days(start_date, end_date));
+    }
+    if let Some(pos) = s.find("..") {
+        let (start_date, start_clock) = parse_date_and_time(&s[..pos])?;
+        let (end_date, end_clock) = parse_date_and_time(&s[pos + 2..])?;
+        let (start_date, end_date) = match (start_date, end_date) {
+            (Some(a), Some(b)) => (a, b),
+            (Some(a), None) => (a.clone(), a),
+            (None, Some(b)) => (b.clone(), b),
+            (None, None) => anyhow::bail!("date range must have a date on at least one side"),
+        };
+        Ok(DateRange {
+            start_date,
+            end_date,
+            start_clock,
+            end_clock,
+        })
+    } else {
+        let (date, clock) = parse_date(s)?;
+        Ok(DateRange {
+            start_date: date.clone(),
+            end_date: date,
+            start_clock: clock,
+            end_clock: None,
+        })
+    }
+}
+
+fn resolve_date(
+    dt: &RequestedDate,
+    relative_to: NaiveDate,
+    weekday_start_at: NaiveDate,
+) -> NaiveDate {
+    match dt {
+        RequestedDate::Today => relative_to,
+        RequestedDate::Tomorrow => relative_to + Duration::days(1),
+        RequestedDate::RelativeDays(n) => relative_to + Duration::days((*n).into()),
+        RequestedDate::Weekday(wanted) => {
+            let mut date = weekday_start_at;
+            while date.weekday() != *wanted {
+                date += Duration::days(1);
+            }
+            date
+        }
+        RequestedDate::Absolute(d) => *d,
+        RequestedDate::Instant(dt) => dt.date_naive(),
+        RequestedDate::OffsetDays(inner, offset) => {
+            resolve_date(inner, relative_to, weekday_start_at) + Duration::days(*offset)
+        }
+        RequestedDate::RelativeWeek {
+            weeks_offset,
+            weekday,
+        } => {
+            let monday_this_week =
+                relative_to - Duration::days(relative_to.weekday().num_days_from_monday().into());
+            let mut date = monday_this_week + Duration::weeks((*weeks_offset).into());
+            while date.weekday() != *weekday {
+                date += Duration::days(1);
+            }
+            date
+        }
+        RequestedDate::NextWeekday(wanted) => {
+            let mut date = relative_to + Duration::days(1);
+            while date.weekday() != *wanted {
+                date += Duration::days(1);
+            }
+            date
+        }
+        RequestedDate::LastWeekday(wanted) => {
+            let mut date = relative_to - Duration::days(1);
+            while date.weekday() != *wanted {
+                date -= Duration::days(1);
+            }
+            date
+        }
+        RequestedDate::Weekend => {
+            let mut date = relative_to;
+            while date.weekday() != Weekday::Sat {
+                date += Duration::days(1);
+            }
+            date
+        }
+        RequestedDate::MonthDay { month, day } => resolve_month_day(*month, *day, relative_to),
+    }
+}
+
+/// Resolve a month/day to a concrete date: the next occurrence on or after
+/// `relative_to`, searching forward a year at a time (so "Feb 29" rolls to
+/// the next leap year if necessary).
+fn resolve_month_day(month: u32, day: u32, relative_to: NaiveDate) -> NaiveDate {
+    let first_occurrence_from = |start_year: i32| -> NaiveDate {
+        (start_year..)
+            .find_map(|year| NaiveDate::from_ymd_opt(year, month, day))
+            .expect("month/day validated at parse time")
+    };
+    let date = first_occurrence_from(relative_to.year());
+    if date < relative_to {
+        first_occurrence_from(relative_to.year() + 1)
+    } else {
+        date
+    }
+}
+
+/// Detect the host's local IANA timezone: honor a `TZ` environment variable naming a zone
+/// `chrono_tz` knows about first, then fall back to the OS-reported zone, then to UTC if
+/// neither source yields a name `chrono_tz` recognizes.
+pub fn detect_local_timezone() -> Tz {
+    std::env::var("TZ")
+        .ok()
+        .and_then(|name| name.parse::<Tz>().ok())
+        .or_else(|| {
+            iana_time_zone::get_timezone()
+                .ok()
+                .and_then(|name| name.parse::<Tz>().ok())
+        })
+        .unwrap_or(Tz::UTC)
+}
+
+/// Resolve the timezone to interpret dates in: an explicit override if given,
+/// otherwise the host's local zone (with a UTC fallback). This lets the CLI
+/// "just work" without the user knowing their tz database name.
+pub fn resolve_timezone(explicit: Option<&str>) -> anyhow::Result<Tz> {
+    match explicit {
+        Some(s) => s
+            .parse::<Tz>()
+            .map_err(|_| anyhow::anyhow!("unknown --timezone: {s}")),
+        None => Ok(detect_local_timezone()),
+    }
+}
+
+/// Localize a naive local midnight that marks the *start* of a range.
+///
+/// If the naive time falls in a DST gap (doesn't exist), advance minute by
+/// minute until a valid instant is found, so the range starts at the first
+/// real moment of the day. If it's ambiguous (occurs twice, e.g. during a
+/// fall-back transition), prefer the earlier instant so the range covers
+/// the whole local day.
+fn localize_start(timezone: Tz, mut naive: NaiveDateTime) -> DateTime<FixedOffset> {
+    loop {
+        match timezone.from_local_datetime(&naive) {
+            LocalResult::Single(dt) => return dt.fixed_offset(),
+            LocalResult::Ambiguous(earliest, _) => return earliest.fixed_offset(),
+            LocalResult::None => naive += Duration::minutes(1),
+        }
+    }
+}
+
+/// Localize a naive local midnight that marks the *end* of a range.
+///
+/// If the naive time falls in a DST gap, step backward until a valid
+/// instant is found, so the range doesn't overshoot into the following
+/// day. If it's ambiguous, prefer the later instant so the range covers
+/// the whole local day.
+fn localize_end(timezone: Tz, mut naive: NaiveDateTime) -> DateTime<FixedOffset> {
+    loop {
+        match timezone.from_local_datetime(&naive) {
+            LocalResult::Single(dt) => return dt.fixed_offset(),
+            LocalResult::Ambiguous(_, latest) => return latest.fixed_offset(),
+            LocalResult::None => naive -= Duration::minutes(1),
+        }
+    }
+}
+
+/// Convert an inclusive date range to a half-open time interval.
+///
+/// Input dates are inclusive (e.g., "mon..wed" means Monday through Wednesday).
+/// Output is a half-open interval `[start, end)` suitable for filtering hourly data.
+/// The start time is clamped to `relative_to` to avoid showing past hours.
+///
+/// `start_clock`/`end_clock` restrict the range to part of each boundary day: when
+/// given, they replace the default midnight/next-midnight bounds so e.g. "today 6..18"
+/// covers only 6am through 6pm instead of the whole day.
+pub fn resolve_time_range(
+    range: DateRange,
+    timezone: Tz,
+    relative_to: DateTime<FixedOffset>,
+) -> (DateTime<FixedOffset>, DateTime<FixedOffset>) {
+    let DateRange {
+        mut start_date,
+        mut end_date,
+        start_clock,
+        end_clock,
+    } = range;
+    let original_date = relative_to.date_naive();
+
+    // Open-Meteo provides forecasts at hour starts, so after 23:00 there's no more data
+    // for "today". Since start is clamped to `relative_to`, shift to "tomorrow" to avoid
+    // an empty forecast. We use 22:55 as the cutoff to account for network latency.
+    const CUTOFF_TIME: NaiveTime = NaiveTime::from_hms_opt(22, 55, 0).unwrap();
+
+    if relative_to.time() > CUTOFF_TIME {
+        if start_date == RequestedDate::Today {
+            start_date = RequestedDate::Tomorrow;
+        }
+        if end_date == RequestedDate::Today {
+            end_date = RequestedDate::Tomorrow;
+        }
+    }
+
+    // We've updated start and end date, but still pass the original relative_to to
+    // resolve_date(), so that "+2" or "thursday" refer to the correct date.
+    let start_resolved = resolve_date(&start_date, original_date, original_date);
+    let end_resolved = resolve_date(&end_date, original_date, start_resolved);
+
+    // An `Instant` carries its own UTC offset: honor it exactly rather than
+    // re-interpreting it as a whole local day in `timezone`.
+    let start_time = match start_date {
+        RequestedDate::Instant(dt) => dt,
+        _ => localize_start(
+            timezone,
+            start_resolved.and_time(start_clock.unwrap_or(NaiveTime::MIN)),
+        ),
+    };
+    // Only clamp a start that falls on today: that's "today, partway through" and
+    // should skip already-elapsed hours. An explicit past date (e.g. "-1")
+    // must stay untouched so it can be served from the historical archive.
+    let start_time = if start_resolved == original_date {
+        std::cmp::max(start_time, relative_to)
+    } else {
+        start_time
+    };
+
+    let end_time = match end_date {
+        RequestedDate::Instant(dt) => dt,
+        _ => match end_clock {
+            // An explicit end time is an exact bound on `end_resolved`'s day,
+            // not an invitation to roll over to the next one.
+            Some(clock) => localize_end(timezone, end_resolved.and_time(clock)),
+            None => {
+                // A `Weekend` end extends through Sunday rather than just its own day.
+                let end_span = if end_date == RequestedDate::Weekend {
+                    Duration::days(2)
+                } else {
+                    Duration::days(1)
+                };
+                let end_resolved = end_resolved + end_span;
+                localize_end(timezone, end_resolved.and_time(NaiveTime::MIN))
+            }
+        },
+    };
+
+    (start_time, end_time)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Timelike;
+
+    fn make_time(hour: u32, minute: u32) -> DateTime<FixedOffset> {
+        // Use a Wednesday (2025-01-15) as the reference date for weekday tests
+        FixedOffset::east_opt(0)
+            .unwrap()
+            .with_ymd_and_hms(2025, 1, 15, hour, minute, 0)
+            .unwrap()
+    }
+
+    // --- parse_date tests ---
+
+    #[test]
+    fn parse_date_today_tomorrow() {
+        assert_eq!(parse_date("today").unwrap().0, RequestedDate::Today);
+        assert_eq!(parse_date("tomorrow").unwrap().0, RequestedDate::Tomorrow);
+    }
+
+    #[test]
+    fn parse_date_case_insensitive() {
+        assert_eq!(parse_date("TODAY").unwrap().0, RequestedDate::Today);
+        assert_eq!(parse_date("Tomorrow").unwrap().0, RequestedDate::Tomorrow);
+        assert_eq!(
+            parse_date("MONDAY").unwrap().0,
+            RequestedDate::Weekday(Weekday::Mon)
+        );
+    }
+
+    #[test]
+    fn parse_date_weekdays() {
+        assert_eq!(
+            parse_date("mon").unwrap().0,
+            RequestedDate::Weekday(Weekday::Mon)
+        );
+        assert_eq!(
+            parse_date("monday").unwrap().0,
+            RequestedDate::Weekday(Weekday::Mon)
+        );
+        assert_eq!(
+            parse_date("tue").unwrap().0,
+            RequestedDate::Weekday(Weekday::Tue)
+        );
+        assert_eq!(
+            parse_date("wed").unwrap().0,
+            RequestedDate::Weekday(Weekday::Wed)
+        );
+        assert_eq!(
+            parse_date("thu").unwrap().0,
+            RequestedDate::Weekday(Weekday::Thu)
+        );
+        assert_eq!(
+            parse_date("fri").unwrap().0,
+            RequestedDate::Weekday(Weekday::Fri)
+        );
+        assert_eq!(
+            parse_date("sat").unwrap().0,
+            RequestedDate::Weekday(Weekday::Sat)
+        );
+        assert_eq!(
+            parse_date("sun").unwrap().0,
+            RequestedDate::Weekday(Weekday::Sun)
+        );
+        assert_eq!(
+            parse_date("sunday").unwrap().0,
+            RequestedDate::Weekday(Weekday::Sun)
+        );
+    }
+
+    #[test]
+    fn parse_date_relative_days() {
+        assert_eq!(parse_date("+0").unwrap().0, RequestedDate::RelativeDays(0));
+        assert_eq!(parse_date("+1").unwrap().0, RequestedDate::RelativeDays(1));
+        assert_eq!(parse_date("+7").unwrap().0, RequestedDate::RelativeDays(7));
+        assert_eq!(
+            parse_date("+16").unwrap().0,
+            RequestedDate::RelativeDays(16)
+        );
+        assert_eq!(
+            parse_date("-3").unwrap().0,
+            RequestedDate::OffsetDays(Box::new(RequestedDate::Today), -3)
+        );
+    }
+
+    #[test]
+    fn parse_date_absolute() {
+        assert_eq!(
+            parse_date("2025-01-15").unwrap().0,
+            RequestedDate::Absolute(NaiveDate::from_ymd_opt(2025, 1, 15).unwrap())
+        );
+        assert_eq!(
+            parse_date("2024-12-31").unwrap().0,
+            RequestedDate::Absolute(NaiveDate::from_ymd_opt(2024, 12, 31).unwrap())
+        );
+    }
+
+    #[test]
+    fn parse_date_invalid() {
+        assert!(parse_date("").is_err());
+        assert!(parse_date("yesterday").is_err());
+        assert!(parse_date("15-01-2025").is_err()); // wrong order
+        assert!(parse_date("2025/01/15").is_err()); // wrong separator
+        assert!(parse_date("invalid").is_err());
+    }
+
+    #[test]
+    fn parse_date_rfc3339_instant() {
+        let expected = FixedOffset::west_opt(8 * 3600)
+            .unwrap()
+            .with_ymd_and_hms(2025, 10, 13, 23, 0, 0)
+            .unwrap();
+        assert_eq!(
+            parse_date("2025-10-13T23:00:00-08:00").unwrap().0,
+            RequestedDate::Instant(expected)
+        );
+    }
+
+    #[test]
+    fn parse_date_bare_date_with_offset() {
+        let expected = FixedOffset::west_opt(8 * 3600)
+            .unwrap()
+            .with_ymd_and_hms(2025, 10, 13, 0, 0, 0)
+            .unwrap();
+        assert_eq!(
+            parse_date("2025-10-13-08:00").unwrap().0,
+            RequestedDate::Instant(expected)
+        );
+    }
+
+    #[test]
+    fn parse_date_modified_weekday() {
+        assert_eq!(
+            parse_date("this friday").unwrap().0,
+            RequestedDate::Weekday(Weekday::Fri)
+        );
+        assert_eq!(
+            parse_date("next monday").unwrap().0,
+            RequestedDate::NextWeekday(Weekday::Mon)
+        );
+        assert_eq!(
+            parse_date("last fri").unwrap().0,
+            RequestedDate::LastWeekday(Weekday::Fri)
+        );
+        assert_eq!(
+            parse_date("NEXT Monday").unwrap().0,
+            RequestedDate::NextWeekday(Weekday::Mon)
+        );
+    }
+
+    #[test]
+    fn parse_date_weekend() {
+        assert_eq!(parse_date("weekend").unwrap().0, RequestedDate::Weekend);
+        assert_eq!(
+            parse_date("this weekend").unwrap().0,
+            RequestedDate::Weekend
+        );
+    }
+
+    #[test]
+    fn parse_date_relative_count() {
+        assert_eq!(
+            parse_date("in 5 days").unwrap().0,
+            RequestedDate::OffsetDays(Box::new(RequestedDate::Today), 5)
+        );
+        assert_eq!(
+            parse_date("in 1 day").unwrap().0,
+            RequestedDate::OffsetDays(Box::new(RequestedDate::Today), 1)
+        );
+        assert_eq!(
+            parse_date("3 days ago").unwrap().0,
+            RequestedDate::OffsetDays(Box::new(RequestedDate::Today), -3)
+        );
+        assert_eq!(
+            parse_date("1 day ago").unwrap().0,
+            RequestedDate::OffsetDays(Box::new(RequestedDate::Today), -1)
+        );
+    }
+
+    #[test]
+    fn parse_date_month_day() {
+        assert_eq!(
+            parse_date("july 4").unwrap().0,
+            RequestedDate::MonthDay { month: 7, day: 4 }
+        );
+        assert_eq!(
+            parse_date("4 july").unwrap().0,
+            RequestedDate::MonthDay { month: 7, day: 4 }
+        );
+        assert_eq!(
+            parse_date("Nov 5th").unwrap().0,
+            RequestedDate::MonthDay { month: 11, day: 5 }
+        );
+        assert!(parse_date("feb 30").is_err());
+    }
+
+    #[test]
+    fn parse_date_with_clock_time() {
+        let (date, time) = parse_date("tomorrow@09:00").unwrap();
+        assert_eq!(date, RequestedDate::Tomorrow);
+        assert_eq!(time, NaiveTime::from_hms_opt(9, 0, 0));
+
+        let (date, time) = parse_date("today@noon").unwrap();
+        assert_eq!(date, RequestedDate::Today);
+        assert_eq!(time, NaiveTime::from_hms_opt(12, 0, 0));
+
+        assert!(parse_date("today@bogus").is_err());
+    }
+
+    // --- parse_date_range tests ---
+
+    #[test]
+    fn parse_date_range_single() {
+        let range = parse_date_range("today").unwrap();
+        assert_eq!(range.start_date, RequestedDate::Today);
+        assert_eq!(range.end_date, RequestedDate::Today);
+        assert_eq!(range.start_clock, None);
+        assert_eq!(range.end_clock, None);
+    }
+
+    #[test]
+    fn parse_date_range_range() {
+        let range = parse_date_range("today..tomorrow").unwrap();
+        assert_eq!(range.start_date, RequestedDate::Today);
+        assert_eq!(range.end_date, RequestedDate::Tomorrow);
+
+        let range = parse_date_range("mon..fri").unwrap();
+        assert_eq!(range.start_date, RequestedDate::Weekday(Weekday::Mon));
+        assert_eq!(range.end_date, RequestedDate::Weekday(Weekday::Fri));
+
+        let range = parse_date_range("+1..+3").unwrap();
+        assert_eq!(range.start_date, RequestedDate::RelativeDays(1));
+        assert_eq!(range.end_date, RequestedDate::RelativeDays(3));
+    }
+
+    #[test]
+    fn parse_date_range_invalid() {
+        assert!(parse_date_range("invalid..today").is_err());
+        assert!(parse_date_range("today..invalid").is_err());
+        assert!(parse_date_range("..today").is_err());
+        assert!(parse_date_range("today..").is_err());
+    }
+
+    #[test]
+    fn parse_date_range_count_days() {
+        let range = parse_date_range("next 7 days").unwrap();
+        assert_eq!(range.start_date, RequestedDate::Today);
+        assert_eq!(
+            range.end_date,
+            RequestedDate::OffsetDays(Box::new(RequestedDate::Today), 6)
+        );
+
+        let range = parse_date_range("last 3 days").unwrap();
+        assert_eq!(
+            range.start_date,
+            RequestedDate::OffsetDays(Box::new(RequestedDate::Today), -2)
+        );
+        assert_eq!(range.end_date, RequestedDate::Today);
+
+        // Case-insensitive, and "day" (singular) is also accepted.
+        let range = parse_date_range("NEXT 1 day").unwrap();
+        assert_eq!(range.start_date, RequestedDate::Today);
+        assert_eq!(
+            range.end_date,
+            RequestedDate::OffsetDays(Box::new(RequestedDate::Today), 0)
+        );
+    }
+
+    #[test]
+    fn parse_date_range_named_weeks() {
+        let range = parse_date_range("this week").unwrap();
+        assert_eq!(
+            range.start_date,
+            RequestedDate::RelativeWeek {
+                weeks_offset: 0,
+                weekday: Weekday::Mon
+            }
+        );
+        assert_eq!(
+            range.end_date,
+            RequestedDate::RelativeWeek {
+                weeks_offset: 0,
+                weekday: Weekday::Sun
+            }
+        );
+
+        let range = parse_date_range("next week").unwrap();
+        assert_eq!(
+            range.start_date,
+            RequestedDate::RelativeWeek {
+                weeks_offset: 1,
+                weekday: Weekday::Mon
+            }
+        );
+    }
+
+    #[test]
+    fn parse_date_range_iso_interval() {
+        let range = parse_date_range("2025-01-15/2025-01-20").unwrap();
+        assert_eq!(
+            range.start_date,
+            RequestedDate::Absolute(NaiveDate::from_ymd_opt(2025, 1, 15).unwrap())
+        );
+        assert_eq!(
+            range.end_date,
+            RequestedDate::Absolute(NaiveDate::from_ymd_opt(2025, 1, 20).unwrap())
+        );
+
+        let range = parse_date_range("2025-01-15/P3D").unwrap();
+        assert_eq!(
+            range.start_date,
+            RequestedDate::Absolute(NaiveDate::from_ymd_opt(2025, 1, 15).unwrap())
+        );
+        assert_eq!(
+            range.end_date,
+            RequestedDate::OffsetDays(Box::new(range.start_date.clone()), 2)
+        );
+    }
+
+    #[test]
+    fn parse_date_range_iso_interval_invalid() {
+        assert!(parse_date_range("invalid/2025-01-20").is_err());
+        assert!(parse_date_range("2025-01-15/invalid").is_err());
+    }
+
+    #[test]
+    fn parse_date_range_time_of_day() {
+        let range = parse_date_range("today 6..18").unwrap();
+        assert_eq!(range.start_date, RequestedDate::Today);
+        assert_eq!(range.end_date, RequestedDate::Today);
+        assert_eq!(range.start_clock, NaiveTime::from_hms_opt(6, 0, 0));
+        assert_eq!(range.end_clock, NaiveTime::from_hms_opt(18, 0, 0));
+
+        let range = parse_date_range("tomorrow@09:00..tomorrow@21:00").unwrap();
+        assert_eq!(range.start_date, RequestedDate::Tomorrow);
+        assert_eq!(range.end_date, RequestedDate::Tomorrow);
+        assert_eq!(range.start_clock, NaiveTime::from_hms_opt(9, 0, 0));
+        assert_eq!(range.end_clock, NaiveTime::from_hms_opt(21, 0, 0));
+
+        let range = parse_date_range("mon 8..fri 20").unwrap();
+        assert_eq!(range.start_date, RequestedDate::Weekday(Weekday::Mon));
+        assert_eq!(range.end_date, RequestedDate::Weekday(Weekday::Fri));
+        assert_eq!(range.start_clock, NaiveTime::from_hms_opt(8, 0, 0));
+        assert_eq!(range.end_clock, NaiveTime::from_hms_opt(20, 0, 0));
+    }
+
+    // --- resolve_time_range tests ---
+
+    /// Test helper that parses a date range string and resolves it in UTC.
+    fn test_resolve(
+        dates: &str,
+        relative_to: DateTime<FixedOffset>,
+    ) -> (DateTime<FixedOffset>, DateTime<FixedOffset>) {
+        let date_range = parse_date_range(dates).unwrap();
+        resolve_time_range(date_range, chrono_tz::UTC, relative_to)
+    }
+
+    #[test]
+    fn resolve_time_range_today_before_cutoff() {
+        let relative_to = make_time(12, 0); // noon
+        let (start, end) = test_resolve("today", relative_to);
+        // Start should be clamped to relative_to (noon)
+        assert_eq!(start.hour(), 12);
+        // End should be midnight of the next day
+        assert_eq!(
+            end.date_naive(),
+            NaiveDate::from_ymd_opt(2025, 1, 16).unwrap()
+        );
+        assert_eq!(end.hour(), 0);
+    }
+
+    #[test]
+    fn resolve_time_range_today_after_cutoff() {
+        let relative_to = make_time(23, 0); // after 22:55
+        let (start, end) = test_resolve("today", relative_to);
+        // "today" should shift to tomorrow due to cutoff
+        assert_eq!(
+            start.date_naive(),
+            NaiveDate::from_ymd_opt(2025, 1, 16).unwrap()
+        );
+        assert_eq!(start.hour(), 0);
+        // End should be midnight of the day after tomorrow
+        assert_eq!(
+            end.date_naive(),
+            NaiveDate::from_ymd_opt(2025, 1, 17).unwrap()
+        );
+    }
+
+    #[test]
+    fn resolve_time_range_at_cutoff_boundary() {
+        // Exactly at 22:55 should NOT trigger the shift (we use >)
+        let (start, _) = test_resolve("today", make_time(22, 55));
+        assert_eq!(
+            start.date_naive(),
+            NaiveDate::from_ymd_opt(2025, 1, 15).unwrap()
+        );
+
+        // One minute later should trigger the shift
+        let (start, _) = test_resolve("today", make_time(22, 56));
+        assert_eq!(
+            start.date_naive(),
+            NaiveDate::from_ymd_opt(2025, 1, 16).unwrap()
+        );
+    }
+
+    #[test]
+    fn resolve_time_range_past_date_not_clamped() {
+        // Reference is Wednesday 2025-01-15 at noon. "-1" (yesterday)
+        // resolves to a date before today, so unlike "today" it must not
+        // be clamped forward to `relative_to` -- it needs to stay a real
+        // past instant so it can be served from the historical archive.
+        let (start, end) = test_resolve("-1..today", make_time(12, 0));
+        assert_eq!(
+            start.date_naive(),
+            NaiveDate::from_ymd_opt(2025, 1, 14).unwrap()
+        );
+        assert_eq!(start.hour(), 0);
+        assert_eq!(
+            end.date_naive(),
+            NaiveDate::from_ymd_opt(2025, 1, 16).unwrap()
+        );
+    }
+
+    #[test]
+    fn resolve_time_range_relative_days() {
+        let (start, end) = test_resolve("+2..+3", make_time(10, 0));
+        // +2 from 2025-01-15 is 2025-01-17
+        assert_eq!(
+            start.date_naive(),
+            NaiveDate::from_ymd_opt(2025, 1, 17).unwrap()
+        );
+        // +3 from 2025-01-15 is 2025-01-18, end is midnight of next day
+        assert_eq!(
+            end.date_naive(),
+            NaiveDate::from_ymd_opt(2025, 1, 19).unwrap()
+        );
+    }
+
+    #[test]
+    fn resolve_time_range_weekday() {
+        // Reference is Wednesday 2025-01-15
+        let (start, end) = test_resolve("fri..sun", make_time(10, 0));
+        // Friday after Wednesday 2025-01-15 is 2025-01-17
+        assert_eq!(
+            start.date_naive(),
+            NaiveDate::from_ymd_opt(2025, 1, 17).unwrap()
+        );
+        // Sunday after Friday is 2025-01-19, end is midnight of next day
+        assert_eq!(
+            end.date_naive(),
+            NaiveDate::from_ymd_opt(2025, 1, 20).unwrap()
+        );
+    }
+
+    #[test]
+    fn resolve_time_range_absolute_ignores_cutoff() {
+        let relative_to = make_time(23, 30); // after cutoff
+        let (start, end) = test_resolve("2025-01-15", relative_to);
+        // Absolute dates should not be affected by the cutoff
+        // But start is still clamped to relative_to
+        assert_eq!(start.hour(), 23);
+        assert_eq!(start.minute(), 30);
+        assert_eq!(
+            end.date_naive(),
+            NaiveDate::from_ymd_opt(2025, 1, 16).unwrap()
+        );
+    }
+
+    #[test]
+    fn resolve_time_range_start_clamped_to_relative_to() {
+        // If relative_to is in the afternoon, start should be clamped
+        let (start, _) = test_resolve("today", make_time(15, 30));
+        assert_eq!(start.hour(), 15);
+        assert_eq!(start.minute(), 30);
+    }
+
+    #[test]
+    fn resolve_time_range_respects_timezone() {
+        // 10:00 UTC on 2025-01-15
+        let relative_to = FixedOffset::east_opt(0)
+            .unwrap()
+            .with_ymd_and_hms(2025, 1, 15, 10, 0, 0)
+            .unwrap();
+
+        // In UTC, "tomorrow" starts at 2025-01-16 00:00:00 UTC
+        let (start_utc, _) = resolve_time_range(
+            parse_date_range("tomorrow").unwrap(),
+            chrono_tz::UTC,
+            relative_to,
+        );
+        assert_eq!(
+            start_utc.date_naive(),
+            NaiveDate::from_ymd_opt(2025, 1, 16).unwrap()
+        );
+        assert_eq!(start_utc.hour(), 0);
+        assert_eq!(start_utc.offset().local_minus_utc(), 0);
+
+        // In Europe/Zagreb (UTC+1 in winter), "tomorrow" starts at 2025-01-16 00:00:00
+        // local, which is 2025-01-15 23:00:00 UTC
+        let (start_zagreb, _) = resolve_time_range(
+            parse_date_range("tomorrow").unwrap(),
+            chrono_tz::Europe::Zagreb,
+            relative_to,
+        );
+        assert_eq!(
+            start_zagreb.date_naive(),
+            NaiveDate::from_ymd_opt(2025, 1, 16).unwrap()
+        );
+        assert_eq!(start_zagreb.hour(), 0);
+        assert_eq!(start_zagreb.offset().local_minus_utc(), 3600); // UTC+1
+
+        // The Zagreb time should be 1 hour earlier in absolute terms
+        assert_eq!(start_zagreb.timestamp(), start_utc.timestamp() - 3600);
+    }
+
+    #[test]
+    fn resolve_time_range_dst_gap_at_midnight() {
+        // Brazil's DST start in 2018 skipped local midnight: clocks jumped from
+        // 2018-11-03 23:59:59 straight to 2018-11-04 01:00:00.
+        let relative_to = FixedOffset::west_opt(2 * 3600)
+            .unwrap()
+            .with_ymd_and_hms(2018, 11, 3, 10, 0, 0)
+            .unwrap();
+
+        let (start, end) = resolve_time_range(
+            DateRange::whole_days(
+                RequestedDate::Absolute(NaiveDate::from_ymd_opt(2018, 11, 4).unwrap()),
+                RequestedDate::Absolute(NaiveDate::from_ymd_opt(2018, 11, 4).unwrap()),
+            ),
+            chrono_tz::America::Sao_Paulo,
+            relative_to,
+        );
+
+        // Start snaps forward to the first real instant of the day, 01:00 local.
+        assert_eq!(
+            start.date_naive(),
+            NaiveDate::from_ymd_opt(2018, 11, 4).unwrap()
+        );
+        assert_eq!(start.hour(), 1);
+
+        // The end boundary (midnight of the following day) is a normal instant.
+        assert_eq!(
+            end.date_naive(),
+            NaiveDate::from_ymd_opt(2018, 11, 5).unwrap()
+        );
+        assert_eq!(end.hour(), 0);
+    }
+
+    #[test]
+    fn resolve_time_range_dst_ambiguous_midnight() {
+        // Brazil's DST end in 2019 made local midnight occur twice: clocks fell back
+        // from 2019-02-17 00:00:00 (DST) to 2019-02-16 23:00:00 (standard time), so
+        // 2019-02-16 00:00:00 itself was never repeated, but 2019-02-17's midnight was.
+        let relative_to = FixedOffset::west_opt(2 * 3600)
+            .unwrap()
+            .with_ymd_and_hms(2019, 2, 16, 10, 0, 0)
+            .unwrap();
+
+        let (_, end) = resolve_time_range(
+            DateRange::whole_days(
+                RequestedDate::Absolute(NaiveDate::from_ymd_opt(2019, 2, 16).unwrap()),
+                RequestedDate::Absolute(NaiveDate::from_ymd_opt(2019, 2, 16).unwrap()),
+            ),
+            chrono_tz::America::Sao_Paulo,
+            relative_to,
+        );
+
+        // The end boundary is midnight of 2019-02-17, which is ambiguous; we should
+        // pick the later (standard time) instance so the range covers the whole day.
+        assert_eq!(
+            end.date_naive(),
+            NaiveDate::from_ymd_opt(2019, 2, 17).unwrap()
+        );
+        assert_eq!(end.hour(), 0);
+        assert_eq!(end.offset().local_minus_utc(), -3 * 3600);
+    }
+
+    #[test]
+    fn resolve_time_range_dst_ambiguous_start() {
+        // Cuba's DST end in 2019 made local midnight occur twice: clocks fell back
+        // from 2019-11-03 00:59:59 (CDT, -04:00) to 2019-11-03 00:00:00 (CST,
+        // -05:00). Requesting 2019-11-03 as the *start* of the range should pick
+        // the earlier (CDT) instance so the range covers the whole local day.
+        let relative_to = FixedOffset::west_opt(2 * 3600)
+            .unwrap()
+            .with_ymd_and_hms(2019, 11, 2, 10, 0, 0)
+            .unwrap();
+
+        let (start, _) = resolve_time_range(
+            DateRange::whole_days(
+                RequestedDate::Absolute(NaiveDate::from_ymd_opt(2019, 11, 3).unwrap()),
+                RequestedDate::Absolute(NaiveDate::from_ymd_opt(2019, 11, 3).unwrap()),
+            ),
+            chrono_tz::America::Havana,
+            relative_to,
+        );
+
+        assert_eq!(
+            start.date_naive(),
+            NaiveDate::from_ymd_opt(2019, 11, 3).unwrap()
+        );
+        assert_eq!(start.hour(), 0);
+        assert_eq!(start.offset().local_minus_utc(), -4 * 3600);
+    }
+
+    #[test]
+    fn resolve_time_range_count_days() {
+        // Reference is Wednesday 2025-01-15
+        let (start, end) = test_resolve("next 7 days", make_time(10, 0));
+        assert_eq!(
+            start.date_naive(),
+            NaiveDate::from_ymd_opt(2025, 1, 15).unwrap()
+        );
+        // 7 days inclusive of today ends with midnight after day 6 (Jan 21)
+        assert_eq!(
+            end.date_naive(),
+            NaiveDate::from_ymd_opt(2025, 1, 22).unwrap()
+        );
+
+        // "last 3 days" resolves to Jan 13-15; its start (Jan 13) is in the
+        // past and is left untouched so it can be served from the archive.
+        let (start, end) = test_resolve("last 3 days", make_time(10, 0));
+        assert_eq!(
+            start.date_naive(),
+            NaiveDate::from_ymd_opt(2025, 1, 13).unwrap()
+        );
+        assert_eq!(
+            end.date_naive(),
+            NaiveDate::from_ymd_opt(2025, 1, 16).unwrap()
+        );
+    }
+
+    #[test]
+    fn resolve_time_range_named_weeks() {
+        // Reference is Wednesday 2025-01-15, whose week runs Jan 13-19.
+        // Monday (Jan 13) is in the past and is left untouched so the
+        // elapsed part of the week can be served from the archive.
+        let (start, end) = test_resolve("this week", make_time(10, 0));
+        assert_eq!(
+            start.date_naive(),
+            NaiveDate::from_ymd_opt(2025, 1, 13).unwrap()
+        );
+        assert_eq!(
+            end.date_naive(),
+            NaiveDate::from_ymd_opt(2025, 1, 20).unwrap()
+        );
+
+        let (start, end) = test_resolve("next week", make_time(10, 0));
+        assert_eq!(
+            start.date_naive(),
+            NaiveDate::from_ymd_opt(2025, 1, 20).unwrap()
+        );
+        assert_eq!(
+            end.date_naive(),
+            NaiveDate::from_ymd_opt(2025, 1, 27).unwrap()
+        );
+    }
+
+    #[test]
+    fn resolve_time_range_weekend() {
+        // Reference is Wednesday 2025-01-15; the upcoming Saturday is Jan 18,
+        // and the range extends through Sunday Jan 19.
+        let (start, end) = test_resolve("weekend", make_time(10, 0));
+        assert_eq!(
+            start.date_naive(),
+            NaiveDate::from_ymd_opt(2025, 1, 18).unwrap()
+        );
+        assert_eq!(
+            end.date_naive(),
+            NaiveDate::from_ymd_opt(2025, 1, 20).unwrap()
+        );
+
+        let (start, end) = test_resolve("this weekend", make_time(10, 0));
+        assert_eq!(
+            start.date_naive(),
+            NaiveDate::from_ymd_opt(2025, 1, 18).unwrap()
+        );
+        assert_eq!(
+            end.date_naive(),
+            NaiveDate::from_ymd_opt(2025, 1, 20).unwrap()
+        );
+    }
+
+    #[test]
+    fn resolve_time_range_modified_weekday() {
+        // Reference is Wednesday 2025-01-15.
+        let (start, end) = test_resolve("next monday", make_time(10, 0));
+        assert_eq!(
+            start.date_naive(),
+            NaiveDate::from_ymd_opt(2025, 1, 20).unwrap()
+        );
+        assert_eq!(
+            end.date_naive(),
+            NaiveDate::from_ymd_opt(2025, 1, 21).unwrap()
+        );
+
+        // "last friday" (Jan 10) is in the past and is left untouched so
+        // it can be served from the archive.
+        let (start, _) = test_resolve("last friday", make_time(10, 0));
+        assert_eq!(
+            start.date_naive(),
+            NaiveDate::from_ymd_opt(2025, 1, 10).unwrap()
+        );
+    }
+
+    #[test]
+    fn resolve_time_range_month_day() {
+        // Reference is 2025-01-15; "july 4" is still ahead this year.
+        let (start, end) = test_resolve("july 4", make_time(10, 0));
+        assert_eq!(
+            start.date_naive(),
+            NaiveDate::from_ymd_opt(2025, 7, 4).unwrap()
+        );
+        assert_eq!(
+            end.date_naive(),
+            NaiveDate::from_ymd_opt(2025, 7, 5).unwrap()
+        );
+
+        // "jan 1" has already passed this year, so it rolls to next year.
+        let (start, end) = test_resolve("jan 1", make_time(10, 0));
+        assert_eq!(
+            start.date_naive(),
+            NaiveDate::from_ymd_opt(2026, 1, 1).unwrap()
+        );
+        assert_eq!(
+            end.date_naive(),
+            NaiveDate::from_ymd_opt(2026, 1, 2).unwrap()
+        );
+    }
+
+    #[test]
+    fn resolve_time_range_iso_interval() {
+        let (start, end) = test_resolve("2025-01-15/P3D", make_time(10, 0));
+        assert_eq!(
+            start.date_naive(),
+            NaiveDate::from_ymd_opt(2025, 1, 15).unwrap()
+        );
+        assert_eq!(
+            end.date_naive(),
+            NaiveDate::from_ymd_opt(2025, 1, 18).unwrap()
+        );
+    }
+
+    #[test]
+    fn resolve_time_range_time_of_day() {
+        // Reference is Wednesday 2025-01-15.
+        let (start, end) = test_resolve("today 6..18", make_time(3, 0));
+        assert_eq!(
+            start.date_naive(),
+            NaiveDate::from_ymd_opt(2025, 1, 15).unwrap()
+        );
+        assert_eq!(start.hour(), 6);
+        assert_eq!(
+            end.date_naive(),
+            NaiveDate::from_ymd_opt(2025, 1, 15).unwrap()
+        );
+        assert_eq!(end.hour(), 18);
+
+        let (start, end) = test_resolve("tomorrow@09:00..tomorrow@21:00", make_time(10, 0));
+        assert_eq!(
+            start.date_naive(),
+            NaiveDate::from_ymd_opt(2025, 1, 16).unwrap()
+        );
+        assert_eq!(start.hour(), 9);
+        assert_eq!(
+            end.date_naive(),
+            NaiveDate::from_ymd_opt(2025, 1, 16).unwrap()
+        );
+        assert_eq!(end.hour(), 21);
+
+        // "18" on its own inherits the date from the other side of the range.
+        let (_, end) = test_resolve("today 6..18", make_time(3, 0));
+        assert_eq!(
+            end.date_naive(),
+            NaiveDate::from_ymd_opt(2025, 1, 15).unwrap()
+        );
+    }
+
+    #[test]
+    fn resolve_time_range_honors_explicit_offset() {
+        // The explicit offset on an RFC3339 instant should be used as-is, even
+        // though `timezone` is a completely different zone.
+        let start = FixedOffset::west_opt(8 * 3600)
+            .unwrap()
+            .with_ymd_and_hms(2025, 10, 13, 23, 0, 0)
+            .unwrap();
+        let end = FixedOffset::west_opt(8 * 3600)
+            .unwrap()
+            .with_ymd_and_hms(2025, 10, 14, 7, 0, 0)
+            .unwrap();
+
+        let (resolved_start, resolved_end) = resolve_time_range(
+            DateRange::whole_days(RequestedDate::Instant(start), RequestedDate::Instant(end)),
+            chrono_tz::Europe::Zagreb,
+            start,
+        );
+
+        assert_eq!(resolved_start, start);
+        assert_eq!(resolved_end, end);
+    }
+}